@@ -0,0 +1,22 @@
+//! Fuzzes `QvlClient::get_betrayal_evidence` across arbitrary node IDs.
+//! Invariant under test: the two-pass length-then-fill protocol never
+//! hands back evidence it didn't actually fill, and a fill pass that
+//! disagrees with its own length pass (which the mock now deliberately
+//! injects, see `fuzz_mock::qvl_get_betrayal_evidence`) is always surfaced
+//! as `Err` rather than read past the allocated buffer.
+
+use honggfuzz::fuzz;
+use membrane_agent::QvlClient;
+
+fn main() {
+    let client = QvlClient::new().expect("mock QVL init never fails");
+
+    loop {
+        fuzz!(|node_id: u32| {
+            // Err: no evidence, or the mock's two passes disagreed.
+            if let Ok(evidence) = client.get_betrayal_evidence(node_id) {
+                assert!(!evidence.is_empty());
+            }
+        });
+    }
+}
@@ -0,0 +1,35 @@
+//! Fuzzes `QvlClient::issue_slash_signal` with arbitrary target DID, reason
+//! byte, and evidence hash. Invariant under test: the 82-byte signal always
+//! lays out `target_did` at offset 0, `reason` at offset 32, and
+//! `evidence_hash` at offset 33 (pinning the `#[repr(packed)]` layout this
+//! crate's doc comments flagged as uncertain).
+
+use honggfuzz::fuzz;
+use membrane_agent::QvlClient;
+
+fn main() {
+    let client = QvlClient::new().expect("mock QVL init never fails");
+
+    loop {
+        fuzz!(|data: &[u8]| {
+            if data.len() < 65 {
+                return;
+            }
+            let mut target_did = [0u8; 32];
+            target_did.copy_from_slice(&data[0..32]);
+            let reason = data[32];
+            let mut evidence_hash = [0u8; 32];
+            evidence_hash.copy_from_slice(&data[33..65]);
+
+            if let Ok(signal) = client.issue_slash_signal(&target_did, reason, &evidence_hash) {
+                assert_eq!(&signal[0..32], &target_did, "target_did must live at offset 0");
+                assert_eq!(signal[32], reason, "reason must live at offset 32");
+                assert_eq!(
+                    &signal[33..65],
+                    &evidence_hash,
+                    "evidence_hash must live at offset 33"
+                );
+            }
+        });
+    }
+}
@@ -0,0 +1,22 @@
+//! Fuzzes `QvlClient::verify_pop` across arbitrary proof bytes/lengths with
+//! fixed DIDs. Invariant under test: the returned verdict is always inside
+//! the `PopVerdict` enum range, even when the mocked FFI boundary hands back
+//! an out-of-range verdict byte.
+
+use honggfuzz::fuzz;
+use membrane_agent::QvlClient;
+
+fn main() {
+    let client = QvlClient::new().expect("mock QVL init never fails");
+    let sender_did = [0x11u8; 32];
+    let receiver_did = [0x22u8; 32];
+
+    loop {
+        fuzz!(|proof_bytes: &[u8]| {
+            // `verify_pop` must either return a valid `PopVerdict` or a
+            // `QvlError` — never panic, and never produce a verdict the
+            // `PopVerdict` enum can't represent.
+            let _ = client.verify_pop(proof_bytes, &sender_did, &receiver_did);
+        });
+    }
+}
@@ -1,9 +1,19 @@
 fn main() {
-    // Link against Zig QVL FFI shared library
+    println!("cargo:rerun-if-env-changed=CARGO_FEATURE_FUZZ_MOCK");
+
+    // `fuzz_mock` swaps the `extern "C"` block in `qvl_ffi.rs` for an
+    // in-crate mock (see that file), so there's nothing to link against;
+    // skip it entirely rather than requiring fuzz builds to also have the
+    // real Zig archive on disk.
+    if std::env::var_os("CARGO_FEATURE_FUZZ_MOCK").is_some() {
+        return;
+    }
+
+    // Link against the Zig QVL FFI static library.
     let sdk_root = std::env::var("CARGO_MANIFEST_DIR")
         .expect("CARGO_MANIFEST_DIR not set");
     let lib_path = format!("{}/../zig-out/lib", sdk_root);
-    
+
     println!("cargo:rustc-link-search=native={}", lib_path);
     println!("cargo:rustc-link-lib=static=qvl_ffi");
     println!("cargo:rerun-if-changed=../zig-out/lib/libqvl_ffi.a");
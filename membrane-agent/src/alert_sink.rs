@@ -0,0 +1,197 @@
+//! Alert Sinks - streaming emitted alerts to external subscribers
+//!
+//! `AnomalyAlertSystem` fans every `Alert` out to any sinks registered via
+//! `register_sink`. `LocalSocketAlertSink` is the "ship it off the node"
+//! sink: it binds a Unix socket and streams every alert, framed the same
+//! way as the L0 IPC protocol (see `event_listener`), to whichever
+//! monitors have connected and subscribed. A subscriber that can't keep up
+//! is disconnected rather than allowed to apply backpressure.
+
+use crate::anomaly_alerts::{Alert, AlertSink};
+use crate::qvl_ffi::AnomalyReason;
+use std::future::Future;
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use tokio::io::AsyncWriteExt;
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+/// IPC protocol magic number, shared with `event_listener`'s L0 framing so
+/// the same Magic/Type/Flags/Length header shape works on both sockets.
+const IPC_MAGIC: u16 = 0x55AA;
+
+/// Event type for an Alert frame on the subscription socket. The L0
+/// listener's event types (Identify/PacketReceived/ConnectionEstablished/
+/// ConnectionDropped) occupy `0x00`-`0x03` on a different socket; this
+/// value only needs to be unique within the alert-subscription protocol.
+const EVENT_TYPE_ALERT: u8 = 0x10;
+
+/// Outbound buffer size per connected subscriber. A subscriber this far
+/// behind is dropped instead of stalling `publish`.
+const SUBSCRIBER_BUFFER: usize = 256;
+
+fn reason_to_u8(reason: AnomalyReason) -> u8 {
+    match reason {
+        AnomalyReason::None => 0,
+        AnomalyReason::NegativeCycle => 1,
+        AnomalyReason::LowCoverage => 2,
+        AnomalyReason::BpDivergence => 3,
+        AnomalyReason::Unknown => 4,
+    }
+}
+
+/// Serialize an alert's fields: priority(1) + node(4) + score(8) +
+/// reason(1) + unix timestamp seconds(8), all little-endian.
+fn encode_alert(alert: &Alert) -> Vec<u8> {
+    let mut payload = Vec::with_capacity(22);
+    payload.push(alert.priority as u8);
+    payload.extend_from_slice(&alert.node.to_le_bytes());
+    payload.extend_from_slice(&alert.score.to_le_bytes());
+    payload.push(reason_to_u8(alert.reason));
+    payload.extend_from_slice(&alert.timestamp.timestamp().to_le_bytes());
+    payload
+}
+
+fn build_frame(event_type: u8, payload: &[u8]) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(8 + payload.len());
+    frame.extend_from_slice(&IPC_MAGIC.to_le_bytes());
+    frame.push(event_type);
+    frame.push(0); // flags, unused
+    frame.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    frame.extend_from_slice(payload);
+    frame
+}
+
+/// Streams every published `Alert` to Unix-socket subscribers. Bind with
+/// `spawn`, then hand the result to `AnomalyAlertSystem::register_sink`.
+pub struct LocalSocketAlertSink {
+    socket_path: String,
+    subscribers: Mutex<Vec<mpsc::Sender<Vec<u8>>>>,
+}
+
+impl LocalSocketAlertSink {
+    /// Bind `socket_path` and start accepting subscriber connections in the
+    /// background. Returns immediately; the accept loop runs for as long
+    /// as the returned `Arc` (or a clone of it) is alive.
+    pub fn spawn(socket_path: impl Into<String>) -> Arc<Self> {
+        let sink = Arc::new(Self {
+            socket_path: socket_path.into(),
+            subscribers: Mutex::new(Vec::new()),
+        });
+        tokio::spawn(sink.clone().accept_loop());
+        sink
+    }
+
+    async fn accept_loop(self: Arc<Self>) {
+        if Path::new(&self.socket_path).exists() {
+            let _ = std::fs::remove_file(&self.socket_path);
+        }
+
+        let listener = match UnixListener::bind(&self.socket_path) {
+            Ok(listener) => listener,
+            Err(e) => {
+                warn!("Alert subscription socket '{}' failed to bind: {}", self.socket_path, e);
+                return;
+            }
+        };
+
+        info!("📡 Alert subscription socket listening on {}", self.socket_path);
+
+        loop {
+            match listener.accept().await {
+                Ok((stream, _addr)) => {
+                    let (tx, rx) = mpsc::channel(SUBSCRIBER_BUFFER);
+                    self.subscribers.lock().unwrap().push(tx);
+                    tokio::spawn(forward_to_subscriber(stream, rx));
+                }
+                Err(e) => warn!("Alert subscription accept failed: {}", e),
+            }
+        }
+    }
+}
+
+async fn forward_to_subscriber(mut stream: UnixStream, mut rx: mpsc::Receiver<Vec<u8>>) {
+    while let Some(frame) = rx.recv().await {
+        if stream.write_all(&frame).await.is_err() {
+            break;
+        }
+    }
+}
+
+impl AlertSink for LocalSocketAlertSink {
+    fn publish<'a>(&'a self, alert: &'a Alert) -> Pin<Box<dyn Future<Output = ()> + Send + 'a>> {
+        Box::pin(async move {
+            let frame = build_frame(EVENT_TYPE_ALERT, &encode_alert(alert));
+            let mut subscribers = self.subscribers.lock().unwrap();
+            subscribers.retain(|tx| match tx.try_send(frame.clone()) {
+                Ok(()) => true,
+                Err(mpsc::error::TrySendError::Full(_)) => {
+                    warn!("Alert subscriber buffer full; dropping subscriber");
+                    false
+                }
+                Err(mpsc::error::TrySendError::Closed(_)) => false,
+            });
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::anomaly_alerts::AlertPriority;
+    use chrono::Utc;
+
+    fn sample_alert() -> Alert {
+        Alert {
+            timestamp: Utc::now(),
+            priority: AlertPriority::Critical,
+            node: 7,
+            score: 0.95,
+            reason: AnomalyReason::NegativeCycle,
+            sender_did: Some([7u8; 32]),
+        }
+    }
+
+    #[test]
+    fn test_encode_alert_round_trips_fixed_fields() {
+        let alert = sample_alert();
+        let payload = encode_alert(&alert);
+
+        assert_eq!(payload.len(), 22);
+        assert_eq!(payload[0], AlertPriority::Critical as u8);
+        assert_eq!(u32::from_le_bytes([payload[1], payload[2], payload[3], payload[4]]), 7);
+        assert_eq!(payload[13], 1); // NegativeCycle
+    }
+
+    #[test]
+    fn test_build_frame_header() {
+        let frame = build_frame(EVENT_TYPE_ALERT, &[0xAA, 0xBB]);
+        assert_eq!(u16::from_le_bytes([frame[0], frame[1]]), IPC_MAGIC);
+        assert_eq!(frame[2], EVENT_TYPE_ALERT);
+        assert_eq!(u32::from_le_bytes([frame[4], frame[5], frame[6], frame[7]]), 2);
+    }
+
+    #[tokio::test]
+    async fn test_publish_delivers_frame_to_subscriber() {
+        let dir = std::env::temp_dir().join(format!("alert_sink_test_{}", std::process::id()));
+        let sink = LocalSocketAlertSink::spawn(dir.to_string_lossy().to_string());
+
+        // Give the accept loop a moment to bind before connecting.
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        let mut client = UnixStream::connect(&dir).await.expect("connect");
+
+        // Let the accept loop register the new subscriber before publishing.
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        sink.publish(&sample_alert()).await;
+
+        use tokio::io::AsyncReadExt;
+        let mut header = [0u8; 8];
+        client.read_exact(&mut header).await.expect("read header");
+        assert_eq!(u16::from_le_bytes([header[0], header[1]]), IPC_MAGIC);
+        assert_eq!(header[2], EVENT_TYPE_ALERT);
+
+        let _ = std::fs::remove_file(&dir);
+    }
+}
@@ -1,12 +1,31 @@
 //! Anomaly Alert System - P0/P1 prioritized alerting
 //!
 //! Emits and tracks critical security alerts from QVL betrayal detection.
+//! Usable under `no_std` (with `alloc`) so it can run on constrained
+//! membrane nodes that still link the Zig QVL library but have no full std.
 
 use crate::qvl_ffi::{AnomalyScore, AnomalyReason};
+use alloc::vec::Vec;
 use chrono::{DateTime, Utc};
+
+#[cfg(feature = "std")]
 use std::sync::{Arc, Mutex};
+
+#[cfg(not(feature = "std"))]
+use alloc::sync::Arc;
+#[cfg(not(feature = "std"))]
+use spin::Mutex;
+
+#[cfg(feature = "std")]
 use tracing::{error, warn, info};
 
+#[cfg(feature = "std")]
+use std::future::Future;
+#[cfg(feature = "std")]
+use std::pin::Pin;
+#[cfg(feature = "std")]
+use tokio::sync::mpsc;
+
 /// Alert priority level
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum AlertPriority {
@@ -18,6 +37,44 @@ pub enum AlertPriority {
     Info = 2,
 }
 
+/// Source of "the current time" for alert timestamps. There is no system
+/// clock under `no_std`, so callers supply one instead of this module
+/// reaching for `chrono::Utc::now()` directly.
+pub trait Clock {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// `Clock` backed by the system clock, for `std` builds.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UtcClock;
+
+#[cfg(feature = "std")]
+impl Clock for UtcClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// A destination for every `Alert` an `AnomalyAlertSystem` emits, so a
+/// real operator can get critical betrayal alerts off the node instead of
+/// only reading them back out of the in-process counters. `publish` must
+/// not block waiting on a slow or disconnected subscriber; implementations
+/// should buffer or drop internally rather than stall the caller, since
+/// `AnomalyAlertSystem` awaits `publish` on a dedicated task per sink, not
+/// inline in `emit`.
+#[cfg(feature = "std")]
+pub trait AlertSink: Send + Sync {
+    fn publish<'a>(&'a self, alert: &'a Alert) -> Pin<Box<dyn Future<Output = ()> + Send + 'a>>;
+}
+
+/// Bounded buffer size for the per-sink forwarding channel. An `emit` call
+/// never blocks on a sink: once this many alerts are already queued for a
+/// sink that hasn't kept up, the new alert is dropped for that sink (with
+/// a `warn!`) rather than applying backpressure to the detection loop.
+#[cfg(feature = "std")]
+const DEFAULT_SINK_BUFFER: usize = 256;
+
 /// Security alert
 #[derive(Clone, Debug)]
 pub struct Alert {
@@ -26,10 +83,18 @@ pub struct Alert {
     pub node: u32,
     pub score: f64,
     pub reason: AnomalyReason,
+    /// The node's DID, when the caller could resolve one (see
+    /// `AnomalyAlertSystem::emit_for_did`). Used to render a human-readable
+    /// identifier instead of the bare numeric node ID.
+    pub sender_did: Option<[u8; 32]>,
 }
 
 impl Alert {
-    fn from_anomaly(anomaly: AnomalyScore) -> Self {
+    fn from_anomaly(
+        anomaly: AnomalyScore,
+        sender_did: Option<[u8; 32]>,
+        clock: &(dyn Clock + Send + Sync),
+    ) -> Self {
         let priority = if anomaly.score >= 0.9 {
             AlertPriority::Critical
         } else if anomaly.score >= 0.7 {
@@ -37,13 +102,25 @@ impl Alert {
         } else {
             AlertPriority::Info
         };
-        
+
         Self {
-            timestamp: Utc::now(),
+            timestamp: clock.now(),
             priority,
             node: anomaly.node,
             score: anomaly.score,
             reason: anomaly.reason,
+            sender_did,
+        }
+    }
+
+    /// Short human-readable identifier for this alert's node: the DID
+    /// dictionary-word rendering when a DID was resolved, otherwise the
+    /// bare node ID.
+    #[cfg(feature = "std")]
+    fn node_display(&self) -> alloc::string::String {
+        match &self.sender_did {
+            Some(did) => crate::did_display::did_display(did),
+            None => alloc::format!("node#{}", self.node),
         }
     }
 }
@@ -52,101 +129,196 @@ impl Alert {
 pub struct AnomalyAlertSystem {
     alerts: Arc<Mutex<Vec<Alert>>>,
     max_alerts: usize,
+    clock: Arc<dyn Clock + Send + Sync>,
+
+    // Registered fan-out destinations, each fed by its own bounded channel
+    // so a slow or disconnected sink can't stall `emit`. Only available
+    // under `std`, since publishing needs a tokio task per sink.
+    #[cfg(feature = "std")]
+    sinks: std::sync::Mutex<Vec<mpsc::Sender<Alert>>>,
 }
 
 impl AnomalyAlertSystem {
-    /// Create new alert system
+    /// Create new alert system, using the system clock
+    #[cfg(feature = "std")]
     pub fn new() -> Self {
-        Self {
-            alerts: Arc::new(Mutex::new(Vec::new())),
-            max_alerts: 1000,
-        }
+        Self::with_clock(Arc::new(UtcClock))
     }
-    
-    /// Create with custom capacity
+
+    /// Create with custom capacity, using the system clock
+    #[cfg(feature = "std")]
     pub fn with_capacity(max_alerts: usize) -> Self {
+        Self::with_capacity_and_clock(max_alerts, Arc::new(UtcClock))
+    }
+
+    /// Create a new alert system backed by a caller-supplied `Clock`. Use
+    /// this under `no_std`, where there is no system clock to default to.
+    pub fn with_clock(clock: Arc<dyn Clock + Send + Sync>) -> Self {
+        Self::with_capacity_and_clock(1000, clock)
+    }
+
+    /// Create with custom capacity and a caller-supplied `Clock`.
+    pub fn with_capacity_and_clock(max_alerts: usize, clock: Arc<dyn Clock + Send + Sync>) -> Self {
         Self {
             alerts: Arc::new(Mutex::new(Vec::with_capacity(max_alerts))),
             max_alerts,
+            clock,
+            #[cfg(feature = "std")]
+            sinks: std::sync::Mutex::new(Vec::new()),
         }
     }
-    
-    /// Emit an alert from anomaly score
+
+    /// Register a sink to receive every future alert, using
+    /// `DEFAULT_SINK_BUFFER` for its forwarding channel.
+    #[cfg(feature = "std")]
+    pub fn register_sink(&self, sink: Arc<dyn AlertSink>) {
+        self.register_sink_with_buffer(sink, DEFAULT_SINK_BUFFER);
+    }
+
+    /// Register a sink with a custom forwarding-channel buffer size. Spawns
+    /// a dedicated task that awaits `sink.publish` for each alert handed to
+    /// it, so `emit` itself never awaits a sink and can't be slowed down by
+    /// one.
+    #[cfg(feature = "std")]
+    pub fn register_sink_with_buffer(&self, sink: Arc<dyn AlertSink>, buffer: usize) {
+        let (tx, mut rx) = mpsc::channel::<Alert>(buffer);
+        tokio::spawn(async move {
+            while let Some(alert) = rx.recv().await {
+                sink.publish(&alert).await;
+            }
+        });
+        self.sinks.lock().unwrap().push(tx);
+    }
+
+    /// Emit an alert from an anomaly score, with no DID resolvable for it.
+    /// Prefer `emit_for_did` when the caller has one (e.g. via
+    /// `DidRegistry`), so the alert renders a human-readable identifier
+    /// instead of a bare node ID.
     pub fn emit(&self, anomaly: AnomalyScore) {
-        let alert = Alert::from_anomaly(anomaly);
-        
-        // Log based on priority
+        self.emit_for_did(anomaly, None)
+    }
+
+    /// Emit an alert from an anomaly score, attaching the node's DID when
+    /// known so logs and sink rendering show a `did_display` identifier
+    /// instead of a bare node ID.
+    pub fn emit_for_did(&self, anomaly: AnomalyScore, sender_did: Option<[u8; 32]>) {
+        let alert = Alert::from_anomaly(anomaly, sender_did, self.clock.as_ref());
+        #[cfg(feature = "std")]
+        let node_display = alert.node_display();
+
+        // Log based on priority (only when a `tracing` subscriber can
+        // actually exist, i.e. on `std` builds)
         match alert.priority {
             AlertPriority::Critical => {
+                #[cfg(feature = "std")]
                 error!(
                     "🚨 P0 CRITICAL ANOMALY: node={}, score={:.3}, reason={:?}",
-                    alert.node, alert.score, alert.reason
+                    node_display, alert.score, alert.reason
                 );
             }
             AlertPriority::Warning => {
+                #[cfg(feature = "std")]
                 warn!(
                     "⚠️  P1 WARNING: node={}, score={:.3}, reason={:?}",
-                    alert.node, alert.score, alert.reason
+                    node_display, alert.score, alert.reason
                 );
             }
             AlertPriority::Info => {
+                #[cfg(feature = "std")]
                 info!(
                     "ℹ️  P2 INFO: node={}, score={:.3}, reason={:?}",
-                    alert.node, alert.score, alert.reason
+                    node_display, alert.score, alert.reason
                 );
             }
         }
-        
+
         // Store alert
+        #[cfg(feature = "std")]
         let mut alerts = self.alerts.lock().unwrap();
-        
+        #[cfg(not(feature = "std"))]
+        let mut alerts = self.alerts.lock();
+
         // Enforce max capacity (FIFO eviction)
         if alerts.len() >= self.max_alerts {
             alerts.remove(0);
         }
-        
-        alerts.push(alert);
+
+        alerts.push(alert.clone());
+        drop(alerts);
+
+        // Fan out to every registered sink. `try_send` never blocks: a
+        // sink whose channel is already full just drops this alert.
+        #[cfg(feature = "std")]
+        {
+            let sinks = self.sinks.lock().unwrap();
+            for tx in sinks.iter() {
+                if tx.try_send(alert.clone()).is_err() {
+                    warn!("Alert sink channel full or closed; dropping alert for it");
+                }
+            }
+        }
     }
-    
+
     /// Get all critical (P0) alerts
     pub fn get_critical_alerts(&self) -> Vec<Alert> {
+        #[cfg(feature = "std")]
         let alerts = self.alerts.lock().unwrap();
+        #[cfg(not(feature = "std"))]
+        let alerts = self.alerts.lock();
+
         alerts
             .iter()
             .filter(|a| a.priority == AlertPriority::Critical)
             .cloned()
             .collect()
     }
-    
+
     /// Get all alerts above a priority threshold
     pub fn get_alerts_above(&self, min_priority: AlertPriority) -> Vec<Alert> {
+        #[cfg(feature = "std")]
         let alerts = self.alerts.lock().unwrap();
+        #[cfg(not(feature = "std"))]
+        let alerts = self.alerts.lock();
+
         alerts
             .iter()
             .filter(|a| a.priority <= min_priority)
             .cloned()
             .collect()
     }
-    
+
     /// Get alert count by priority
     pub fn count_by_priority(&self, priority: AlertPriority) -> usize {
+        #[cfg(feature = "std")]
         let alerts = self.alerts.lock().unwrap();
+        #[cfg(not(feature = "std"))]
+        let alerts = self.alerts.lock();
+
         alerts.iter().filter(|a| a.priority == priority).count()
     }
-    
+
     /// Clear all alerts
     pub fn clear(&self) {
+        #[cfg(feature = "std")]
         let mut alerts = self.alerts.lock().unwrap();
+        #[cfg(not(feature = "std"))]
+        let mut alerts = self.alerts.lock();
+
         alerts.clear();
     }
-    
+
     /// Get total alert count
     pub fn total_count(&self) -> usize {
+        #[cfg(feature = "std")]
         let alerts = self.alerts.lock().unwrap();
+        #[cfg(not(feature = "std"))]
+        let alerts = self.alerts.lock();
+
         alerts.len()
     }
 }
 
+#[cfg(feature = "std")]
 impl Default for AnomalyAlertSystem {
     fn default() -> Self {
         Self::new()
@@ -156,32 +328,33 @@ impl Default for AnomalyAlertSystem {
 #[cfg(test)]
 mod tests {
     use super::*;
-    
+
     #[test]
     fn test_alert_priority_from_score() {
+        let clock = UtcClock;
         let anomaly_critical = AnomalyScore {
             node: 1,
             score: 0.95,
             reason: AnomalyReason::NegativeCycle,
         };
-        
-        let alert = Alert::from_anomaly(anomaly_critical);
+
+        let alert = Alert::from_anomaly(anomaly_critical, None, &clock);
         assert_eq!(alert.priority, AlertPriority::Critical);
-        
+
         let anomaly_warning = AnomalyScore {
             node: 2,
             score: 0.75,
             reason: AnomalyReason::NegativeCycle,
         };
-        
-        let alert = Alert::from_anomaly(anomaly_warning);
+
+        let alert = Alert::from_anomaly(anomaly_warning, None, &clock);
         assert_eq!(alert.priority, AlertPriority::Warning);
     }
-    
+
     #[test]
     fn test_alert_system_capacity() {
         let system = AnomalyAlertSystem::with_capacity(3);
-        
+
         for i in 0..5 {
             let anomaly = AnomalyScore {
                 node: i,
@@ -190,25 +363,38 @@ mod tests {
             };
             system.emit(anomaly);
         }
-        
+
         // Should only keep last 3 alerts
         assert_eq!(system.total_count(), 3);
     }
-    
+
     #[test]
     fn test_filter_by_priority() {
         let system = AnomalyAlertSystem::new();
-        
+
         // Add mix of priorities
         system.emit(AnomalyScore { node: 1, score: 0.95, reason: AnomalyReason::NegativeCycle });
         system.emit(AnomalyScore { node: 2, score: 0.75, reason: AnomalyReason::LowCoverage });
         system.emit(AnomalyScore { node: 3, score: 0.55, reason: AnomalyReason::BpDivergence });
-        
+
         let critical = system.get_critical_alerts();
         assert_eq!(critical.len(), 1);
         assert_eq!(critical[0].node, 1);
-        
+
         let warnings_and_above = system.get_alerts_above(AlertPriority::Warning);
         assert_eq!(warnings_and_above.len(), 2);
     }
+
+    #[test]
+    fn test_emit_for_did_attaches_resolved_did() {
+        let system = AnomalyAlertSystem::new();
+        let did = [9u8; 32];
+        system.emit_for_did(
+            AnomalyScore { node: 1, score: 0.95, reason: AnomalyReason::NegativeCycle },
+            Some(did),
+        );
+
+        let alerts = system.get_critical_alerts();
+        assert_eq!(alerts[0].sender_did, Some(did));
+    }
 }
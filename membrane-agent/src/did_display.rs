@@ -0,0 +1,125 @@
+//! DID Display - short, human-readable identifiers for 32-byte DIDs
+//!
+//! Event/log lines throughout `main.rs` and the alert paths identify peers
+//! by raw DID byte slices (`&sender_did[..4]`), which are unreadable and
+//! ambiguous for an operator eyeballing logs. `did_display` instead
+//! deterministically renders a DID as a short sequence of dictionary
+//! words: a few sampled directly across the DID, plus a couple more
+//! derived from a hash folded over every byte, so a truncated or mistyped
+//! identifier usually reads as visibly wrong rather than silently passing
+//! for a different peer.
+//!
+//! This is a readability aid for logs, not a collision-resistant
+//! identifier: the rendered string only carries on the order of 50 bits,
+//! far short of the DID's own 256, so two distinct DIDs can in principle
+//! render identically. Anything that needs to actually distinguish peers
+//! must compare the raw DID bytes, never this string.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Adjective half of each dictionary entry; combined with `NOUNS` this
+/// gives a 256-entry fixed dictionary (16 * 16) without hand-authoring 256
+/// distinct words.
+const ADJECTIVES: [&str; 16] = [
+    "swift", "quiet", "bold", "calm", "dark", "bright", "wild", "still",
+    "sharp", "soft", "brave", "sly", "grim", "keen", "proud", "shy",
+];
+
+/// Noun half of each dictionary entry; see `ADJECTIVES`.
+const NOUNS: [&str; 16] = [
+    "fox", "wolf", "hawk", "bear", "lynx", "crow", "stag", "owl",
+    "boar", "crane", "otter", "raven", "moth", "heron", "viper", "wren",
+];
+
+/// Number of dictionary words sampled from the DID, not counting the
+/// trailing checksum words.
+const CHUNK_COUNT: usize = 4;
+
+/// Byte stride between sampled positions: `32 / CHUNK_COUNT`.
+const CHUNK_STRIDE: usize = 32 / CHUNK_COUNT;
+
+/// Number of checksum words appended after the sampled words. Each one
+/// comes from a different byte of `fold_hash`'s output, so two DIDs that
+/// happen to agree on every sampled stride position still need to collide
+/// on a full-DID hash, not just a single XOR byte, to render identically.
+const CHECKSUM_WORD_COUNT: usize = 2;
+
+/// Dictionary entry for `byte`, indexing `ADJECTIVES`/`NOUNS` by its high
+/// and low nibble respectively.
+fn word_for(byte: u8) -> String {
+    format!("{}-{}", ADJECTIVES[(byte >> 4) as usize], NOUNS[(byte & 0x0F) as usize])
+}
+
+/// FNV-1a hash folded over every byte of `did`. Not cryptographic, just
+/// cheap whole-DID mixing so the checksum words depend on all 32 bytes
+/// instead of only the sampled ones.
+fn fold_hash(did: &[u8; 32]) -> u32 {
+    const FNV_OFFSET_BASIS: u32 = 0x811c_9dc5;
+    const FNV_PRIME: u32 = 0x0100_0193;
+    did.iter()
+        .fold(FNV_OFFSET_BASIS, |hash, &b| (hash ^ b as u32).wrapping_mul(FNV_PRIME))
+}
+
+/// Render `did` as `CHUNK_COUNT` dictionary words sampled at a fixed
+/// stride across the DID, followed by `CHECKSUM_WORD_COUNT` words derived
+/// from `fold_hash`. Deterministic: the same DID always renders the same
+/// string, and corrupting any byte (sampled or not) is very likely to
+/// change at least one checksum word. See the module doc comment for what
+/// this scheme does and does not guarantee.
+pub fn did_display(did: &[u8; 32]) -> String {
+    let mut words: Vec<String> = (0..CHUNK_COUNT)
+        .map(|i| word_for(did[i * CHUNK_STRIDE]))
+        .collect();
+
+    let hash = fold_hash(did);
+    words.extend((0..CHECKSUM_WORD_COUNT).map(|i| word_for((hash >> (i * 8)) as u8)));
+
+    words.join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_did_display_is_deterministic() {
+        let did = [7u8; 32];
+        assert_eq!(did_display(&did), did_display(&did));
+    }
+
+    #[test]
+    fn test_did_display_differs_for_different_dids() {
+        let mut a = [0u8; 32];
+        let mut b = [0u8; 32];
+        a[0] = 1;
+        b[0] = 2;
+        assert_ne!(did_display(&a), did_display(&b));
+    }
+
+    #[test]
+    fn test_did_display_checksum_catches_unsampled_corruption() {
+        // Byte 1 isn't one of the sampled stride positions (0, 8, 16, 24),
+        // so only the checksum words should change when it's corrupted.
+        let mut original = [0u8; 32];
+        original[1] = 0xAB;
+        let mut corrupted = original;
+        corrupted[1] = 0xCD;
+
+        let original_display = did_display(&original);
+        let corrupted_display = did_display(&corrupted);
+        assert_ne!(original_display, corrupted_display);
+
+        let original_words: Vec<&str> = original_display.split('/').collect();
+        let corrupted_words: Vec<&str> = corrupted_display.split('/').collect();
+        assert_eq!(original_words[..CHUNK_COUNT], corrupted_words[..CHUNK_COUNT]);
+        assert_ne!(original_words[CHUNK_COUNT..], corrupted_words[CHUNK_COUNT..]);
+    }
+
+    #[test]
+    fn test_did_display_has_expected_word_count() {
+        let did = [0u8; 32];
+        assert_eq!(did_display(&did).split('/').count(), CHUNK_COUNT + CHECKSUM_WORD_COUNT);
+    }
+}
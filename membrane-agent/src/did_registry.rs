@@ -0,0 +1,94 @@
+//! DID <-> NodeID registry
+//!
+//! Bridges two addressing schemes used across the QVL API: `get_trust_score`
+//! (and `should_accept_packet`) key by 32-byte DID, while `detect_betrayal`
+//! and the risk graph key by `u32` node ID, with nothing tying the two
+//! together (see the integration test's "CRITICAL API GAP" note). This
+//! registry is populated when a peer identifies itself over the L0 IPC
+//! Identify frame, so `PolicyEnforcer` can resolve a sender DID to its node
+//! ID and fold betrayal status into the same accept/drop decision.
+
+use crate::qvl_ffi::{QvlClient, QvlError};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// Concurrent bidirectional DID <-> NodeID map.
+pub struct DidRegistry {
+    qvl: Arc<QvlClient>,
+    did_to_node: RwLock<HashMap<[u8; 32], u32>>,
+    node_to_did: RwLock<HashMap<u32, [u8; 32]>>,
+}
+
+impl DidRegistry {
+    pub fn new(qvl: Arc<QvlClient>) -> Self {
+        Self {
+            qvl,
+            did_to_node: RwLock::new(HashMap::new()),
+            node_to_did: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Resolve the node ID for a peer's DID, registering it with QVL (and
+    /// caching the result) the first time it's seen. Called once per
+    /// connection, from the Identify handshake.
+    pub fn register_peer(&self, did: [u8; 32]) -> Result<u32, QvlError> {
+        if let Some(&node_id) = self.did_to_node.read().unwrap().get(&did) {
+            return Ok(node_id);
+        }
+
+        let node_id = self.qvl.register_node(&did)?;
+        self.did_to_node.write().unwrap().insert(did, node_id);
+        self.node_to_did.write().unwrap().insert(node_id, did);
+        Ok(node_id)
+    }
+
+    /// Pure cache lookup: the node ID for a DID already seen via
+    /// `register_peer`, without touching QVL.
+    pub fn node_id_for(&self, did: &[u8; 32]) -> Option<u32> {
+        self.did_to_node.read().unwrap().get(did).copied()
+    }
+
+    /// Pure cache lookup: the DID for a node ID already seen via
+    /// `register_peer`, without touching QVL.
+    pub fn did_for(&self, node_id: u32) -> Option<[u8; 32]> {
+        self.node_to_did.read().unwrap().get(&node_id).copied()
+    }
+
+    /// Snapshot of every node ID seen so far, for callers (e.g. the
+    /// periodic betrayal-detection loop) that need to act on actually-seen
+    /// peers instead of a hardcoded range.
+    pub fn known_node_ids(&self) -> Vec<u32> {
+        self.node_to_did.read().unwrap().keys().copied().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_and_lookup_roundtrip() {
+        let qvl = Arc::new(QvlClient::new().unwrap());
+        let registry = DidRegistry::new(qvl);
+
+        let did = [3u8; 32];
+        let node_id = registry.register_peer(did).expect("register_peer failed");
+
+        assert_eq!(registry.node_id_for(&did), Some(node_id));
+        assert_eq!(registry.did_for(node_id), Some(did));
+        assert_eq!(registry.known_node_ids(), vec![node_id]);
+    }
+
+    #[test]
+    fn test_register_peer_is_idempotent() {
+        let qvl = Arc::new(QvlClient::new().unwrap());
+        let registry = DidRegistry::new(qvl);
+
+        let did = [4u8; 32];
+        let first = registry.register_peer(did).unwrap();
+        let second = registry.register_peer(did).unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(registry.known_node_ids().len(), 1);
+    }
+}
@@ -6,11 +6,24 @@ use tokio::net::{UnixListener, UnixStream};
 use tokio::io::{AsyncReadExt, BufReader};
 use tokio::sync::mpsc;
 use std::path::Path;
-use tracing::{info, error, warn, debug};
+use tracing::{info, error, warn};
+
+use crate::did_registry::DidRegistry;
+use crate::ipc_protocol::protocol_for_version;
+use std::sync::Arc;
 
 /// IPC Protocol Magic Number (0x55AA)
 const IPC_MAGIC: u16 = 0x55AA;
 
+/// Identify frame event type. Must be the first frame a connecting L0
+/// client sends; every other frame type is refused until identification
+/// succeeds.
+const EVENT_TYPE_IDENTIFY: u8 = 0x00;
+
+/// Fixed payload size of an Identify frame: version(1) + network_id(32) +
+/// capabilities(4) + peer_did(32).
+const IDENTIFY_PAYLOAD_LEN: usize = 69;
+
 /// L0 transport events
 #[derive(Debug, Clone)]
 pub enum L0Event {
@@ -20,12 +33,12 @@ pub enum L0Event {
         packet_type: u8,
         payload_size: usize,
     },
-    
+
     /// Connection established (Type 0x02)
     ConnectionEstablished {
         peer_did: [u8; 32],
     },
-    
+
     /// Connection dropped (Type 0x03)
     ConnectionDropped {
         peer_did: [u8; 32],
@@ -40,6 +53,12 @@ pub struct EventListenerConfig {
     pub buffer_size: usize,
     /// Socket path
     pub socket_path: String,
+    /// Network/chain ID this node belongs to. A connecting peer's Identify
+    /// frame must carry the same 32 bytes, or the connection is refused.
+    pub network_id: [u8; 32],
+    /// Protocol versions this listener is willing to speak. The first
+    /// Identify frame on a connection must name one of these.
+    pub supported_versions: Vec<u8>,
 }
 
 impl Default for EventListenerConfig {
@@ -47,6 +66,8 @@ impl Default for EventListenerConfig {
         Self {
             buffer_size: 1000,
             socket_path: "/tmp/libertaria_l0.sock".to_string(),
+            network_id: [0u8; 32],
+            supported_versions: vec![1],
         }
     }
 }
@@ -55,6 +76,7 @@ impl Default for EventListenerConfig {
 pub struct EventListener {
     event_tx: mpsc::Sender<L0Event>,
     config: EventListenerConfig,
+    did_registry: Option<Arc<DidRegistry>>,
 }
 
 impl EventListener {
@@ -65,37 +87,47 @@ impl EventListener {
             Self {
                 event_tx: tx,
                 config,
+                did_registry: None,
             },
             rx,
         )
     }
-    
+
+    /// Register every identifying peer's DID with a shared `DidRegistry`,
+    /// so `PolicyEnforcer` can later resolve the same DID to its node ID.
+    pub fn with_did_registry(mut self, registry: Arc<DidRegistry>) -> Self {
+        self.did_registry = Some(registry);
+        self
+    }
+
     /// Start listening for L0 IPC connections
     pub async fn start(&self) -> Result<(), EventListenerError> {
         // Remove existing socket if it exists
         if Path::new(&self.config.socket_path).exists() {
             let _ = std::fs::remove_file(&self.config.socket_path);
         }
-        
+
         // Ensure parent dir exists (if not /tmp)
         if let Some(parent) = Path::new(&self.config.socket_path).parent() {
             if !parent.exists() {
                 let _ = std::fs::create_dir_all(parent);
             }
         }
-        
+
         let listener = UnixListener::bind(&self.config.socket_path)
             .map_err(|e| EventListenerError::BindFailed(e.to_string()))?;
-            
+
         info!("ðŸŽ§ IPC Server listening on {}", self.config.socket_path);
-        
+
         loop {
             match listener.accept().await {
                 Ok((stream, _addr)) => {
                     info!("ðŸ”Œ L0 Client connected");
                     let tx = self.event_tx.clone();
+                    let config = self.config.clone();
+                    let did_registry = self.did_registry.clone();
                     tokio::spawn(async move {
-                        if let Err(e) = handle_connection(stream, tx).await {
+                        if let Err(e) = handle_connection(stream, tx, config, did_registry).await {
                             warn!("IPC connection error: {}", e);
                         }
                         info!("ðŸ”Œ L0 Client disconnected");
@@ -107,7 +139,7 @@ impl EventListener {
             }
         }
     }
-    
+
     /// Inject a test event (for testing without socket)
     #[cfg(test)]
     pub async fn inject_event(&self, event: L0Event) -> Result<(), EventListenerError> {
@@ -121,76 +153,137 @@ impl EventListener {
     }
 }
 
+/// Read one frame's header and payload off the wire. Doesn't interpret the
+/// payload: callers decode it according to `event_type`.
+async fn read_frame(
+    reader: &mut BufReader<UnixStream>,
+) -> Result<Option<(u8, Vec<u8>)>, EventListenerError> {
+    let mut header_buf = [0u8; 8];
+    match reader.read_exact(&mut header_buf).await {
+        Ok(_) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None), // Clean disconnect
+        Err(e) => return Err(EventListenerError::IoError(e.to_string())),
+    };
+
+    // Deserialize Header: Magic(2), Type(1), Flags(1), Length(4)
+    let magic = u16::from_le_bytes([header_buf[0], header_buf[1]]);
+    let event_type = header_buf[2];
+    let _flags = header_buf[3];
+    let length = u32::from_le_bytes([header_buf[4], header_buf[5], header_buf[6], header_buf[7]]);
+
+    if magic != IPC_MAGIC {
+        warn!("Invalid IPC magic: {:04x}", magic);
+        return Err(EventListenerError::ProtocolError("Invalid Magic".into()));
+    }
+
+    let mut payload = vec![0u8; length as usize];
+    if length > 0 {
+        reader
+            .read_exact(&mut payload)
+            .await
+            .map_err(|e| EventListenerError::IoError(e.to_string()))?;
+    }
+
+    Ok(Some((event_type, payload)))
+}
+
+/// Outcome of a successful Identify handshake.
+struct Identity {
+    version: u8,
+    peer_did: [u8; 32],
+}
+
+/// Require and process the mandatory Identify handshake. Returns the
+/// negotiated protocol version and the peer's DID on success; any other
+/// frame, an unsupported version, or a network ID mismatch drops the
+/// connection with a `ProtocolError` before any other frame is ever
+/// forwarded.
+async fn identify(
+    reader: &mut BufReader<UnixStream>,
+    config: &EventListenerConfig,
+) -> Result<Identity, EventListenerError> {
+    let (event_type, payload) = read_frame(reader)
+        .await?
+        .ok_or_else(|| EventListenerError::ProtocolError("Connection closed before Identify".into()))?;
+
+    if event_type != EVENT_TYPE_IDENTIFY {
+        return Err(EventListenerError::ProtocolError(format!(
+            "First frame must be Identify (0x00), got {:#04x}",
+            event_type
+        )));
+    }
+
+    if payload.len() < IDENTIFY_PAYLOAD_LEN {
+        return Err(EventListenerError::ProtocolError(
+            "Invalid Identify payload size".into(),
+        ));
+    }
+
+    let version = payload[0];
+    let mut network_id = [0u8; 32];
+    network_id.copy_from_slice(&payload[1..33]);
+    let capabilities = u32::from_le_bytes([payload[33], payload[34], payload[35], payload[36]]);
+    let mut peer_did = [0u8; 32];
+    peer_did.copy_from_slice(&payload[37..69]);
+
+    if !config.supported_versions.contains(&version) {
+        return Err(EventListenerError::ProtocolError(format!(
+            "Unsupported protocol version: {}",
+            version
+        )));
+    }
+
+    if network_id != config.network_id {
+        return Err(EventListenerError::ProtocolError(
+            "Network ID mismatch".into(),
+        ));
+    }
+
+    info!(
+        "ðŸª Peer identified: version={} capabilities={:#010x} did={:?}",
+        version, capabilities, &peer_did[..4]
+    );
+
+    Ok(Identity { version, peer_did })
+}
+
 /// Handle a single L0 IPC connection
-async fn handle_connection(stream: UnixStream, tx: mpsc::Sender<L0Event>) -> Result<(), EventListenerError> {
+async fn handle_connection(
+    stream: UnixStream,
+    tx: mpsc::Sender<L0Event>,
+    config: EventListenerConfig,
+    did_registry: Option<Arc<DidRegistry>>,
+) -> Result<(), EventListenerError> {
     let mut reader = BufReader::new(stream);
-    
+
+    // No other frame is honored until the peer identifies itself.
+    let identity = identify(&mut reader, &config).await?;
+    let protocol = protocol_for_version(identity.version).ok_or_else(|| {
+        EventListenerError::ProtocolError(format!(
+            "No decoder for negotiated version {}",
+            identity.version
+        ))
+    })?;
+
+    if let Some(registry) = &did_registry {
+        if let Err(e) = registry.register_peer(identity.peer_did) {
+            warn!("Failed to register identified peer: {}", e);
+        }
+    }
+
     loop {
-        // 1. Read Header (8 bytes)
-        let mut header_buf = [0u8; 8];
-        match reader.read_exact(&mut header_buf).await {
-            Ok(_) => {}, // Continue
-            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => break, // Clean disconnect
-            Err(e) => return Err(EventListenerError::IoError(e.to_string())),
+        let (event_type, payload) = match read_frame(&mut reader).await? {
+            Some(frame) => frame,
+            None => break, // Clean disconnect
         };
-        
-        // Deserialize Header: Magic(2), Type(1), Flags(1), Length(4)
-        let magic = u16::from_le_bytes([header_buf[0], header_buf[1]]);
-        let event_type = header_buf[2];
-        let _flags = header_buf[3];
-        let length = u32::from_le_bytes([header_buf[4], header_buf[5], header_buf[6], header_buf[7]]);
-        
-        if magic != IPC_MAGIC {
-            warn!("Invalid IPC magic: {:04x}", magic);
-            return Err(EventListenerError::ProtocolError("Invalid Magic".into()));
-        }
-        
-        // 2. Read Payload
-        let mut payload = vec![0u8; length as usize];
-        if length > 0 {
-            reader.read_exact(&mut payload).await
-                .map_err(|e| EventListenerError::IoError(e.to_string()))?;
-        }
-            
-        // 3. Parse Event
-        match event_type {
-            0x01 => { // PacketReceived
-                if payload.len() < 37 { // 32 DID + 1 Type + 4 Size
-                    warn!("Invalid PacketReceived payload size: {}", payload.len());
-                    continue; 
-                }
-                let mut did = [0u8; 32];
-                did.copy_from_slice(&payload[0..32]);
-                let p_type = payload[32];
-                let size = u32::from_le_bytes([payload[33], payload[34], payload[35], payload[36]]);
-                
-                let event = L0Event::PacketReceived {
-                    sender_did: did,
-                    packet_type: p_type,
-                    payload_size: size as usize,
-                };
-                
-                if tx.send(event).await.is_err() {
-                    break; // Receiver closed
-                }
-            },
-            0x02 => { // ConnectionEstablished
-                 if payload.len() < 32 {
-                     continue;
-                 }
-                 let mut did = [0u8; 32];
-                 did.copy_from_slice(&payload[0..32]);
-                 let event = L0Event::ConnectionEstablished {
-                     peer_did: did,
-                 };
-                 if tx.send(event).await.is_err() { break; }
-            },
-            _ => {
-                debug!("Unknown event type: {}", event_type);
+
+        if let Some(event) = protocol.decode_event(event_type, &payload)? {
+            if tx.send(event).await.is_err() {
+                break; // Receiver closed
             }
         }
     }
-    
+
     Ok(())
 }
 
@@ -199,13 +292,13 @@ async fn handle_connection(stream: UnixStream, tx: mpsc::Sender<L0Event>) -> Res
 pub enum EventListenerError {
     #[error("Bind failed: {0}")]
     BindFailed(String),
-    
+
     #[error("Protocol error: {0}")]
     ProtocolError(String),
-    
+
     #[error("IO error: {0}")]
     IoError(String),
-    
+
     #[error("Channel closed")]
     ChannelClosed,
 }
@@ -215,25 +308,46 @@ mod tests {
     use super::*;
     use tokio::net::UnixStream;
     use tokio::io::AsyncWriteExt;
-    
+
+    fn identify_frame(config: &EventListenerConfig) -> Vec<u8> {
+        let mut msg = Vec::new();
+        msg.extend_from_slice(&IPC_MAGIC.to_le_bytes());
+        msg.push(EVENT_TYPE_IDENTIFY);
+        msg.push(0x00); // Flags
+        msg.extend_from_slice(&(IDENTIFY_PAYLOAD_LEN as u32).to_le_bytes());
+        msg.push(config.supported_versions[0]); // version
+        msg.extend_from_slice(&config.network_id);
+        msg.extend_from_slice(&0u32.to_le_bytes()); // capabilities
+        msg.extend_from_slice(&[0xAB; 32]); // peer DID
+        msg
+    }
+
     #[tokio::test]
     async fn test_ipc_server() {
-        let mut config = EventListenerConfig::default();
-        config.socket_path = "/tmp/test_ipc.sock".to_string();
-        
+        let config = EventListenerConfig {
+            socket_path: "/tmp/test_ipc.sock".to_string(),
+            ..Default::default()
+        };
+
         let (listener, mut rx) = EventListener::new(config.clone());
-        
+
         // Spawn server
         let server_handle = tokio::spawn(async move {
             listener.start().await.unwrap();
         });
-        
+
         // Wait for server to bind
         tokio::time::sleep(std::time::Duration::from_millis(100)).await;
-        
+
         // Connect client
         let mut stream = UnixStream::connect(&config.socket_path).await.expect("Connect failed");
-        
+
+        // Identify handshake must come first.
+        stream
+            .write_all(&identify_frame(&config))
+            .await
+            .expect("Identify write failed");
+
         // Construct message: Header + Payload
         // Header: Magic(0x55AA), Type(0x01), Flags(0), Len(37)
         let mut msg = Vec::new();
@@ -241,14 +355,14 @@ mod tests {
         msg.push(0x01); // Type=PacketReceived
         msg.push(0x00); // Flags
         msg.extend_from_slice(&37u32.to_le_bytes()); // Length
-        
+
         // Payload: DID(32) + Type(1) + Size(4)
         msg.extend_from_slice(&[0xFF; 32]); // DID
         msg.push(42); // Packet Type
         msg.extend_from_slice(&1024u32.to_le_bytes()); // Payload Size
-        
+
         stream.write_all(&msg).await.expect("Write failed");
-        
+
         // Receive
         let event = rx.recv().await.expect("Receive failed");
         match event {
@@ -258,7 +372,109 @@ mod tests {
             }
             _ => panic!("Wrong event type"),
         }
-        
+
+        server_handle.abort();
+    }
+
+    #[tokio::test]
+    async fn test_ipc_server_rejects_frame_before_identify() {
+        let config = EventListenerConfig {
+            socket_path: "/tmp/test_ipc_no_identify.sock".to_string(),
+            ..Default::default()
+        };
+
+        let (listener, mut rx) = EventListener::new(config.clone());
+
+        let server_handle = tokio::spawn(async move {
+            listener.start().await.unwrap();
+        });
+
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+        let mut stream = UnixStream::connect(&config.socket_path).await.expect("Connect failed");
+
+        // Send a PacketReceived frame without identifying first.
+        let mut msg = Vec::new();
+        msg.extend_from_slice(&0x55AAu16.to_le_bytes());
+        msg.push(0x01);
+        msg.push(0x00);
+        msg.extend_from_slice(&37u32.to_le_bytes());
+        msg.extend_from_slice(&[0xFF; 32]);
+        msg.push(42);
+        msg.extend_from_slice(&1024u32.to_le_bytes());
+        stream.write_all(&msg).await.expect("Write failed");
+
+        // The connection is rejected and dropped before anything is
+        // forwarded. The listener keeps its own sender alive for the life of
+        // `start()`, so the channel never actually closes here -- assert
+        // that nothing arrives within a generous window instead of waiting
+        // on a `None` that would never come.
+        let forwarded = tokio::time::timeout(std::time::Duration::from_millis(500), rx.recv()).await;
+        assert!(forwarded.is_err(), "no event should be forwarded for a connection that never completed Identify");
+
+        server_handle.abort();
+    }
+
+    #[tokio::test]
+    async fn test_ipc_server_rejects_network_id_mismatch() {
+        let config = EventListenerConfig {
+            socket_path: "/tmp/test_ipc_bad_network.sock".to_string(),
+            ..Default::default()
+        };
+
+        let (listener, mut rx) = EventListener::new(config.clone());
+
+        let server_handle = tokio::spawn(async move {
+            listener.start().await.unwrap();
+        });
+
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+        let mut stream = UnixStream::connect(&config.socket_path).await.expect("Connect failed");
+
+        let mut mismatched = config.clone();
+        mismatched.network_id = [0xFFu8; 32];
+        stream
+            .write_all(&identify_frame(&mismatched))
+            .await
+            .expect("Identify write failed");
+
+        let forwarded = tokio::time::timeout(std::time::Duration::from_millis(500), rx.recv()).await;
+        assert!(forwarded.is_err(), "no event should be forwarded for a rejected Identify");
+
+        server_handle.abort();
+    }
+
+    #[tokio::test]
+    async fn test_ipc_server_registers_peer_did() {
+        let config = EventListenerConfig {
+            socket_path: "/tmp/test_ipc_did_registry.sock".to_string(),
+            ..Default::default()
+        };
+
+        let qvl = Arc::new(crate::qvl_ffi::QvlClient::new().unwrap());
+        let registry = Arc::new(DidRegistry::new(qvl));
+
+        let (listener, _rx) = EventListener::new(config.clone());
+        let listener = listener.with_did_registry(registry.clone());
+
+        let server_handle = tokio::spawn(async move {
+            listener.start().await.unwrap();
+        });
+
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+        let mut stream = UnixStream::connect(&config.socket_path).await.expect("Connect failed");
+        stream
+            .write_all(&identify_frame(&config))
+            .await
+            .expect("Identify write failed");
+
+        // Give the server a moment to process the handshake.
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+        assert!(registry.node_id_for(&[0xABu8; 32]).is_some());
+
         server_handle.abort();
     }
 }
@@ -0,0 +1,73 @@
+//! Versioned L0 IPC frame decoding.
+//!
+//! The 8-byte header (`Magic/Type/Flags/Length`) is version-independent, but
+//! how a frame's payload maps to an [`L0Event`] is not: a future v2 can grow
+//! the `PacketReceived` payload (e.g. a priority hint or a signature over the
+//! payload) without breaking v1 clients. [`EventListener`](crate::event_listener::EventListener)
+//! negotiates the version during the Identify handshake and picks the
+//! matching [`IpcProtocol`] for the rest of the connection.
+
+use crate::event_listener::{EventListenerError, L0Event};
+use tracing::warn;
+
+/// Decodes frame payloads for one negotiated protocol version.
+pub trait IpcProtocol: Send {
+    /// Decode a single frame's `(event_type, payload)` into an [`L0Event`].
+    /// Returns `Ok(None)` for frame types this version doesn't forward
+    /// (unknown types, or a malformed payload that's logged and skipped);
+    /// returns `Err` only when the connection itself can no longer be
+    /// trusted.
+    fn decode_event(&self, event_type: u8, payload: &[u8]) -> Result<Option<L0Event>, EventListenerError>;
+}
+
+/// Resolve the decoder for a protocol version negotiated during Identify.
+/// Returns `None` for a version nothing here implements yet.
+pub fn protocol_for_version(version: u8) -> Option<Box<dyn IpcProtocol>> {
+    match version {
+        1 => Some(Box::new(v1::V1Protocol)),
+        _ => None,
+    }
+}
+
+/// The original, still-default L0 IPC frame layout.
+pub mod v1 {
+    use super::*;
+
+    /// `PacketReceived` payload: DID(32) + Type(1) + Size(4).
+    /// `ConnectionEstablished` payload: DID(32).
+    pub struct V1Protocol;
+
+    impl IpcProtocol for V1Protocol {
+        fn decode_event(&self, event_type: u8, payload: &[u8]) -> Result<Option<L0Event>, EventListenerError> {
+            match event_type {
+                0x01 => {
+                    if payload.len() < 37 {
+                        warn!("Invalid PacketReceived payload size: {}", payload.len());
+                        return Ok(None);
+                    }
+                    let mut did = [0u8; 32];
+                    did.copy_from_slice(&payload[0..32]);
+                    let packet_type = payload[32];
+                    let size = u32::from_le_bytes([payload[33], payload[34], payload[35], payload[36]]);
+
+                    Ok(Some(L0Event::PacketReceived {
+                        sender_did: did,
+                        packet_type,
+                        payload_size: size as usize,
+                    }))
+                }
+                0x02 => {
+                    if payload.len() < 32 {
+                        warn!("Invalid ConnectionEstablished payload size: {}", payload.len());
+                        return Ok(None);
+                    }
+                    let mut did = [0u8; 32];
+                    did.copy_from_slice(&payload[0..32]);
+
+                    Ok(Some(L0Event::ConnectionEstablished { peer_did: did }))
+                }
+                _ => Ok(None),
+            }
+        }
+    }
+}
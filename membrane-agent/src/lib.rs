@@ -1,16 +1,57 @@
 //! Membrane Agent - L2 Trust-Based Policy Enforcement
 //!
 //! Library components for the Membrane Agent daemon.
+//!
+//! Builds `no_std` (with `alloc`) when the default `std` feature is
+//! disabled, so the FFI wrapper and alert system can run on constrained
+//! membrane nodes/gateways that still link the Zig QVL library but have no
+//! full std. `policy_enforcer`, `event_listener`, and `slash_signing` need a
+//! real OS (sockets, system time via their own clocks, OS RNGs) and are only
+//! built with `std`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
 pub mod qvl_ffi;
-pub mod policy_enforcer;
 pub mod anomaly_alerts;
+pub mod tlv_codec;
+pub mod did_display;
+#[cfg(feature = "std")]
+pub mod policy_enforcer;
+#[cfg(feature = "std")]
+pub mod did_registry;
+#[cfg(feature = "std")]
 pub mod event_listener;
+#[cfg(feature = "std")]
+pub mod ipc_protocol;
+#[cfg(feature = "std")]
+pub mod slash_signing;
+#[cfg(feature = "std")]
+pub mod supervisor;
+#[cfg(feature = "std")]
+pub mod alert_sink;
 
 pub use qvl_ffi::{
     QvlClient, QvlError, AnomalyScore, AnomalyReason,
     PopVerdict, QvlRiskEdge,
 };
+pub use anomaly_alerts::{AnomalyAlertSystem, Alert, AlertPriority, Clock};
+#[cfg(feature = "std")]
+pub use anomaly_alerts::AlertSink;
+pub use tlv_codec::{ProofOfCycle, SlashSignalFields, TlvError};
+pub use did_display::did_display;
+#[cfg(feature = "std")]
 pub use policy_enforcer::{PolicyEnforcer, PolicyDecision};
-pub use anomaly_alerts::{AnomalyAlertSystem, Alert, AlertPriority};
+#[cfg(feature = "std")]
+pub use did_registry::DidRegistry;
+#[cfg(feature = "std")]
 pub use event_listener::{EventListener, EventListenerConfig, L0Event};
+#[cfg(feature = "std")]
+pub use ipc_protocol::{protocol_for_version, IpcProtocol};
+#[cfg(feature = "std")]
+pub use slash_signing::{SignedSlashSignal, SlashSignError, SlashSigner, SlashVerifier};
+#[cfg(feature = "std")]
+pub use supervisor::{BackoffConfig, HealthState, Supervisor};
+#[cfg(feature = "std")]
+pub use alert_sink::LocalSocketAlertSink;
@@ -3,8 +3,8 @@
 //! L2 trust-based policy enforcement daemon for Libertaria.
 
 use membrane_agent::{
-    QvlClient, PolicyEnforcer, AnomalyAlertSystem,
-    EventListener, EventListenerConfig, L0Event, PolicyDecision,
+    did_display, QvlClient, PolicyEnforcer, AnomalyAlertSystem, DidRegistry,
+    EventListenerConfig, L0Event, LocalSocketAlertSink, PolicyDecision, Supervisor,
 };
 use std::sync::Arc;
 use std::time::Duration;
@@ -22,37 +22,49 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     info!("✅ QVL client initialized");
     
     // Initialize components
-    let policy_enforcer = Arc::new(PolicyEnforcer::new(qvl.clone()));
+    let supervisor = Supervisor::new(qvl.clone());
+    let did_registry = Arc::new(DidRegistry::new(qvl.clone()));
+    let policy_enforcer = Arc::new(
+        PolicyEnforcer::new(qvl.clone())
+            .with_did_registry(did_registry.clone())
+            .with_health(supervisor.clone()),
+    );
     let alert_system = Arc::new(AnomalyAlertSystem::new());
+    alert_system.register_sink(LocalSocketAlertSink::spawn("/tmp/libertaria_alerts.sock"));
     let config = EventListenerConfig::default();
-    let (event_listener, mut event_rx) = EventListener::new(config);
-    
+
     info!("✅ Policy enforcer initialized");
     info!("✅ Alert system initialized");
     info!("✅ Event listener initialized");
-    
-    // Spawn event listener task
-    let listener_handle = tokio::spawn(async move {
-        if let Err(e) = event_listener.start().await {
-            error!("Event listener error: {}", e);
-        }
-    });
-    
+
+    // Spawn the supervised L0 event listener: rebinds the socket with
+    // backoff if it fails or its socket file vanishes.
+    let (mut event_rx, listener_handle) = supervisor.clone().spawn_event_listener(
+        config,
+        Some(did_registry.clone()),
+        Duration::from_secs(15),
+    );
+
+    // Spawn periodic QVL health checks, re-initializing the client with
+    // backoff after repeated probe failures.
+    let health_handle = tokio::spawn(supervisor.clone().run_qvl_health_check(Duration::from_secs(15)));
+
     // Spawn periodic betrayal detection
     let qvl_clone = qvl.clone();
     let alerts_clone = alert_system.clone();
+    let registry_clone = did_registry.clone();
     let betrayal_handle = tokio::spawn(async move {
         let mut interval = tokio::time::interval(Duration::from_secs(10));
-        
+
         loop {
             interval.tick().await;
-            
-            // TODO: Get actual node list from registry
-            // For now, check a small set of test nodes
-            for node_id in 0..10 {
+
+            // Check every peer actually seen over the Identify handshake,
+            // instead of a hardcoded node range.
+            for node_id in registry_clone.known_node_ids() {
                 match qvl_clone.detect_betrayal(node_id) {
                     Ok(anomaly) if anomaly.score > 0.5 => {
-                        alerts_clone.emit(anomaly);
+                        alerts_clone.emit_for_did(anomaly, registry_clone.did_for(node_id));
                     }
                     Ok(_) => {}, // No anomaly
                     Err(e) => {
@@ -60,11 +72,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     }
                 }
             }
-            
+
             // Log alert stats every cycle
             let p0_count = alerts_clone.count_by_priority(membrane_agent::AlertPriority::Critical);
             let p1_count = alerts_clone.count_by_priority(membrane_agent::AlertPriority::Warning);
-            
+
             if p0_count > 0 || p1_count > 0 {
                 info!("📊 Alert stats: P0={}, P1={}", p0_count, p1_count);
             }
@@ -72,7 +84,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     });
     
     info!("🚀 Membrane Agent running");
-    info!("   - Event listener: STUB MODE (TODO: L0 integration)");
+    info!("   - Event listener: supervised, auto-reconnecting");
     info!("   - Betrayal detection: every 10 seconds");
     info!("   - Policy enforcement: ready");
     
@@ -86,28 +98,28 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         
                         match decision {
                             PolicyDecision::Accept => {
-                                info!("✅ ACCEPT packet type={} size={} from={:?}", 
-                                    packet_type, payload_size, &sender_did[..4]);
+                                info!("✅ ACCEPT packet type={} size={} from={}",
+                                    packet_type, payload_size, did_display(&sender_did));
                             },
                             PolicyDecision::Deprioritize => {
-                                warn!("⬇️  DEPRIORITIZE packet type={} from={:?}",
-                                    packet_type, &sender_did[..4]);
+                                warn!("⬇️  DEPRIORITIZE packet type={} from={}",
+                                    packet_type, did_display(&sender_did));
                             },
                             PolicyDecision::Drop => {
-                                error!("🚫 DROP packet type={} from={:?}",
-                                    packet_type, &sender_did[..4]);
+                                error!("🚫 DROP packet type={} from={}",
+                                    packet_type, did_display(&sender_did));
                             },
                             PolicyDecision::Neutral => {
-                                info!("⚪ NEUTRAL packet type={} from={:?} (no trust data)",
-                                    packet_type, &sender_did[..4]);
+                                info!("⚪ NEUTRAL packet type={} from={} (no trust data)",
+                                    packet_type, did_display(&sender_did));
                             },
                         }
                     },
                     L0Event::ConnectionEstablished { peer_did } => {
-                        info!("🔗 Connection established with {:?}", &peer_did[..4]);
+                        info!("🔗 Connection established with {}", did_display(&peer_did));
                     },
                     L0Event::ConnectionDropped { peer_did, reason } => {
-                        warn!("❌ Connection dropped with {:?}: {}", &peer_did[..4], reason);
+                        warn!("❌ Connection dropped with {}: {}", did_display(&peer_did), reason);
                     },
                 }
             },
@@ -122,6 +134,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Cleanup
     listener_handle.abort();
     betrayal_handle.abort();
+    health_handle.abort();
     
     info!("Membrane Agent stopped");
     
@@ -2,8 +2,45 @@
 //!
 //! Queries QVL for trust scores and makes policy decisions.
 
+use crate::did_registry::DidRegistry;
 use crate::qvl_ffi::{QvlClient, QvlError};
-use std::sync::Arc;
+use crate::slash_signing::{SignedSlashSignal, SlashSigner};
+use crate::supervisor::{HealthState, Supervisor};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+/// Reason code for `BetrayalNegativeCycle`, as laid out by
+/// `QvlClient::issue_slash_signal`.
+const REASON_BETRAYAL_NEGATIVE_CYCLE: u8 = 1;
+
+/// Default half-life for the decaying local penalty, in seconds.
+const DEFAULT_HALF_LIFE_SECS: f64 = 300.0;
+
+/// Default penalty added for one drop or one betrayal flag.
+const DEFAULT_PENALTY_INCREMENT: f64 = 0.3;
+
+/// Decayed penalty below this is treated as zero and its entry evicted
+/// from `reputation`, rather than kept around forever. Without this, a
+/// peer that rotates DIDs to dodge its own history (or just the steady
+/// trickle of one-off clean reads) would grow the map without bound.
+const PENALTY_EVICTION_EPSILON: f64 = 1e-6;
+
+/// A sender's locally observed misbehavior, decaying back towards zero over
+/// time so a node that stops misbehaving eventually recovers on its own.
+struct Reputation {
+    penalty: f64,
+    last_update: Instant,
+}
+
+impl Reputation {
+    fn fresh() -> Self {
+        Self {
+            penalty: 0.0,
+            last_update: Instant::now(),
+        }
+    }
+}
 
 /// Policy decision for packet handling
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -21,10 +58,30 @@ pub enum PolicyDecision {
 /// Trust-based policy enforcer
 pub struct PolicyEnforcer {
     qvl: Arc<QvlClient>,
-    
+
     // Policy thresholds
     drop_threshold: f64,      // Below this: drop
     untrusted_threshold: f64, // Below this: deprioritize
+
+    // Signer used to authenticate SlashSignals issued by this node. `None`
+    // means `punish_if_guilty` can't produce a broadcastable signal yet.
+    signer: Option<Arc<dyn SlashSigner + Send + Sync>>,
+
+    // Per-DID locally observed penalty, layered on top of the QVL base
+    // trust score (see `should_accept_packet`).
+    reputation: Mutex<HashMap<[u8; 32], Reputation>>,
+    half_life_secs: f64,
+    penalty_increment: f64,
+
+    // Bridges DID-keyed trust lookups and NodeID-keyed betrayal detection.
+    // `None` means `should_accept_packet` can only consult the trust score.
+    did_registry: Option<Arc<DidRegistry>>,
+
+    // Supervises QVL FFI liveness. When attached, `qvl()` prefers its live
+    // client over `qvl` above (which then only matters as the initial
+    // value), and `should_accept_packet` falls back to `Neutral` outright
+    // while a reconnect is in progress.
+    health: Option<Arc<Supervisor>>,
 }
 
 impl PolicyEnforcer {
@@ -34,9 +91,15 @@ impl PolicyEnforcer {
             qvl,
             drop_threshold: 0.1,      // Drop if trust < 0.1
             untrusted_threshold: 0.5, // Deprioritize if trust < 0.5
+            signer: None,
+            reputation: Mutex::new(HashMap::new()),
+            half_life_secs: DEFAULT_HALF_LIFE_SECS,
+            penalty_increment: DEFAULT_PENALTY_INCREMENT,
+            did_registry: None,
+            health: None,
         }
     }
-    
+
     /// Create with custom thresholds
     pub fn with_thresholds(
         qvl: Arc<QvlClient>,
@@ -47,24 +110,157 @@ impl PolicyEnforcer {
             qvl,
             drop_threshold,
             untrusted_threshold,
+            signer: None,
+            reputation: Mutex::new(HashMap::new()),
+            half_life_secs: DEFAULT_HALF_LIFE_SECS,
+            penalty_increment: DEFAULT_PENALTY_INCREMENT,
+            did_registry: None,
+            health: None,
         }
     }
-    
-    /// Decide whether to accept a packet from a DID
+
+    /// Attach a `SlashSigner` so `punish_if_guilty` can emit authenticated,
+    /// network-broadcastable SlashSignals instead of bare bytes.
+    pub fn with_signer(mut self, signer: Arc<dyn SlashSigner + Send + Sync>) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    /// Attach a `DidRegistry` so `should_accept_packet` can resolve a
+    /// sender DID to its node ID and fold in the node's betrayal status.
+    pub fn with_did_registry(mut self, did_registry: Arc<DidRegistry>) -> Self {
+        self.did_registry = Some(did_registry);
+        self
+    }
+
+    /// Override the decaying-penalty half-life and the increment added per
+    /// drop/betrayal flag. Defaults are `DEFAULT_HALF_LIFE_SECS` and
+    /// `DEFAULT_PENALTY_INCREMENT`.
+    pub fn with_reputation_params(mut self, half_life_secs: f64, penalty_increment: f64) -> Self {
+        self.half_life_secs = half_life_secs;
+        self.penalty_increment = penalty_increment;
+        self
+    }
+
+    /// Attach a `Supervisor` so QVL calls use its live, auto-reconnecting
+    /// client instead of the one captured at construction, and so
+    /// `should_accept_packet` can tell a real outage apart from a clean
+    /// "no trust data" lookup.
+    pub fn with_health(mut self, health: Arc<Supervisor>) -> Self {
+        self.health = Some(health);
+        self
+    }
+
+    /// The QVL client to use for this call: the supervisor's live client if
+    /// one is attached, otherwise the client fixed at construction.
+    fn qvl(&self) -> Arc<QvlClient> {
+        match &self.health {
+            Some(health) => health.qvl(),
+            None => self.qvl.clone(),
+        }
+    }
+
+    /// Lazily decay `did`'s stored penalty to the current instant and
+    /// return it, without adding anything. A DID with no entry reads as a
+    /// plain zero without allocating one, and an entry whose decayed
+    /// penalty has dropped below `PENALTY_EVICTION_EPSILON` is removed
+    /// rather than kept around at effectively zero forever.
+    fn decayed_penalty(&self, did: &[u8; 32]) -> f64 {
+        let mut reputation = self.reputation.lock().unwrap();
+        let Some(entry) = reputation.get_mut(did) else {
+            return 0.0;
+        };
+
+        let elapsed_secs = entry.last_update.elapsed().as_secs_f64();
+        entry.penalty *= 0.5f64.powf(elapsed_secs / self.half_life_secs);
+        entry.last_update = Instant::now();
+
+        let penalty = entry.penalty;
+        if penalty < PENALTY_EVICTION_EPSILON {
+            reputation.remove(did);
+            0.0
+        } else {
+            penalty
+        }
+    }
+
+    /// Decay `did`'s stored penalty to now, then add one penalty increment
+    /// for a fresh drop or betrayal flag.
+    fn add_penalty(&self, did: &[u8; 32]) {
+        let mut reputation = self.reputation.lock().unwrap();
+        let entry = reputation.entry(*did).or_insert_with(Reputation::fresh);
+        let elapsed_secs = entry.last_update.elapsed().as_secs_f64();
+        entry.penalty = entry.penalty * 0.5f64.powf(elapsed_secs / self.half_life_secs) + self.penalty_increment;
+        entry.last_update = Instant::now();
+    }
+
+    /// Decide whether to accept a packet from a DID. The QVL base trust
+    /// score is blended with a locally observed, time-decaying penalty
+    /// (see `Reputation`) before the Drop/Deprioritize thresholds apply, so
+    /// a node QVL still rates highly but that is misbehaving right now gets
+    /// deprioritized, recovering automatically as the penalty decays. If a
+    /// `DidRegistry` resolves this DID to a node ID, its current betrayal
+    /// status is folded in too, so a single decision consults both signals
+    /// instead of only the trust score.
     pub fn should_accept_packet(&self, sender_did: &[u8; 32]) -> PolicyDecision {
-        match self.qvl.get_trust_score(sender_did) {
-            Ok(score) if score < self.drop_threshold => PolicyDecision::Drop,
-            Ok(score) if score < self.untrusted_threshold => PolicyDecision::Deprioritize,
-            Ok(_) => PolicyDecision::Accept,
+        // While a supervised QVL client is mid-reconnect, its answers (if
+        // any) aren't trustworthy; fall back to `Neutral` explicitly rather
+        // than let a borrowed-time FFI call fail its own way.
+        if let Some(health) = &self.health {
+            if health.qvl_state() == HealthState::Reconnecting {
+                return PolicyDecision::Neutral;
+            }
+        }
+
+        match self.qvl().get_trust_score(sender_did) {
+            Ok(score) => {
+                let penalty = self.decayed_penalty(sender_did);
+                let effective_trust = score * (1.0 - penalty.min(1.0));
+
+                let mut decision = if effective_trust < self.drop_threshold {
+                    PolicyDecision::Drop
+                } else if effective_trust < self.untrusted_threshold {
+                    PolicyDecision::Deprioritize
+                } else {
+                    PolicyDecision::Accept
+                };
+
+                if decision == PolicyDecision::Accept {
+                    if let Some(node_id) = self.node_id_for(sender_did) {
+                        if self.check_for_betrayal(node_id).is_some() {
+                            decision = PolicyDecision::Deprioritize;
+                        }
+                    }
+                }
+
+                if decision == PolicyDecision::Drop {
+                    self.add_penalty(sender_did);
+                }
+
+                decision
+            }
             Err(QvlError::TrustScoreFailed) | Err(QvlError::InvalidDid) => PolicyDecision::Neutral,
             Err(_) => PolicyDecision::Neutral,
         }
     }
-    
-    /// Check if a node should be flagged for betrayal
+
+    /// Resolve a sender DID to its node ID via the attached `DidRegistry`,
+    /// if any. Pure cache lookup — never calls into QVL.
+    fn node_id_for(&self, did: &[u8; 32]) -> Option<u32> {
+        self.did_registry.as_ref()?.node_id_for(did)
+    }
+
+    /// Check if a node should be flagged for betrayal. A flagged node's DID
+    /// (when resolvable) gets a local penalty increment, same as a dropped
+    /// packet.
     pub fn check_for_betrayal(&self, node_id: u32) -> Option<f64> {
-        match self.qvl.detect_betrayal(node_id) {
-            Ok(anomaly) if anomaly.score > 0.7 => Some(anomaly.score),
+        match self.qvl().detect_betrayal(node_id) {
+            Ok(anomaly) if anomaly.score > 0.7 => {
+                if let Some(did) = self.qvl().get_did(node_id) {
+                    self.add_penalty(&did);
+                }
+                Some(anomaly.score)
+            }
             _ => None,
         }
     }
@@ -79,20 +275,47 @@ impl PolicyEnforcer {
             })
             .collect()
     }
+
+    /// Check a node for betrayal and, if guilty, issue a signed SlashSignal
+    /// ready for network broadcast. Returns `None` if the node isn't
+    /// flagged, its DID can't be resolved, no signer has been configured
+    /// (see `with_signer`), or signing itself fails.
+    pub fn punish_if_guilty(&self, node_id: u32) -> Option<SignedSlashSignal> {
+        self.check_for_betrayal(node_id)?;
+        let target_did = self.qvl().get_did(node_id)?;
+        let signer = self.signer.as_ref()?;
+
+        // TODO: derive this from the real Proof-of-Cycle evidence once the
+        // TLV evidence codec is wired in here; a fixed placeholder keeps
+        // the signal format stable for now.
+        let evidence_hash = [0xEEu8; 32];
+
+        let signal = self
+            .qvl()
+            .issue_slash_signal(&target_did, REASON_BETRAYAL_NEGATIVE_CYCLE, &evidence_hash)
+            .ok()?;
+        signer.sign(signal).ok()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     
+    // `fuzz_mock`'s `qvl_get_trust_score` always returns a flat 0.5 for
+    // every DID (see `qvl_ffi::fuzz_mock`) so the fuzz targets stay focused
+    // on the FFI boundary rather than DID-specific trust semantics; that
+    // collapses the "unknown DID" case this test exercises, so it only runs
+    // against the real backend.
     #[test]
+    #[cfg(not(feature = "fuzz_mock"))]
     fn test_policy_enforcer_neutral() {
         let qvl = Arc::new(QvlClient::new().unwrap());
         let enforcer = PolicyEnforcer::new(qvl);
-        
+
         let unknown_did = [0u8; 32];
         let decision = enforcer.should_accept_packet(&unknown_did);
-        
+
         // Unknown DIDs should be treated as neutral
         assert_eq!(decision, PolicyDecision::Neutral);
     }
@@ -118,4 +341,106 @@ mod tests {
         // Clean graph should have no betrayals
         assert_eq!(betrayals.len(), 0);
     }
+
+    #[test]
+    fn test_reputation_penalty_decays_over_time() {
+        let qvl = Arc::new(QvlClient::new().unwrap());
+        let enforcer = PolicyEnforcer::new(qvl).with_reputation_params(0.001, 0.5);
+
+        let did = [7u8; 32];
+        enforcer.add_penalty(&did);
+        assert!(enforcer.decayed_penalty(&did) > 0.0);
+
+        // Half-life is 1ms here, so after 50ms the penalty should have
+        // decayed to effectively zero.
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        assert!(enforcer.decayed_penalty(&did) < 0.01);
+    }
+
+    #[test]
+    fn test_decayed_penalty_does_not_insert_for_unpenalized_did() {
+        let qvl = Arc::new(QvlClient::new().unwrap());
+        let enforcer = PolicyEnforcer::new(qvl);
+
+        let did = [42u8; 32];
+        assert_eq!(enforcer.decayed_penalty(&did), 0.0);
+        assert!(enforcer.reputation.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_decayed_penalty_evicts_entry_once_below_epsilon() {
+        let qvl = Arc::new(QvlClient::new().unwrap());
+        let enforcer = PolicyEnforcer::new(qvl).with_reputation_params(0.001, 0.5);
+
+        let did = [43u8; 32];
+        enforcer.add_penalty(&did);
+        assert!(!enforcer.reputation.lock().unwrap().is_empty());
+
+        // Half-life is 1ms here, so after 50ms the penalty decays well
+        // below the eviction epsilon and the entry should be removed.
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        assert_eq!(enforcer.decayed_penalty(&did), 0.0);
+        assert!(enforcer.reputation.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_reputation_penalty_accumulates_on_repeat() {
+        let qvl = Arc::new(QvlClient::new().unwrap());
+        let enforcer = PolicyEnforcer::new(qvl).with_reputation_params(3600.0, 0.3);
+
+        let did = [9u8; 32];
+        enforcer.add_penalty(&did);
+        let first = enforcer.decayed_penalty(&did);
+
+        enforcer.add_penalty(&did);
+        let second = enforcer.decayed_penalty(&did);
+
+        assert!(second > first);
+    }
+
+    // See the comment on `test_policy_enforcer_neutral`: `fuzz_mock`'s flat
+    // trust score doesn't distinguish unknown DIDs, so this only runs
+    // against the real backend.
+    #[test]
+    #[cfg(not(feature = "fuzz_mock"))]
+    fn test_should_accept_packet_without_registry_ignores_node_id() {
+        let qvl = Arc::new(QvlClient::new().unwrap());
+        let enforcer = PolicyEnforcer::new(qvl);
+
+        // No DidRegistry attached: an unknown DID stays Neutral regardless
+        // of any node-level betrayal status.
+        let unknown_did = [5u8; 32];
+        assert_eq!(enforcer.should_accept_packet(&unknown_did), PolicyDecision::Neutral);
+    }
+
+    #[test]
+    fn test_should_accept_packet_resolves_node_id_via_registry() {
+        let qvl = Arc::new(QvlClient::new().unwrap());
+        let registry = Arc::new(crate::did_registry::DidRegistry::new(qvl.clone()));
+        let enforcer = PolicyEnforcer::new(qvl).with_did_registry(registry.clone());
+
+        let did = [6u8; 32];
+        let node_id = registry.register_peer(did).expect("register_peer failed");
+
+        // Clean graph: no betrayal, so resolving the node ID shouldn't
+        // change anything relative to a plain trust-score lookup.
+        assert_eq!(enforcer.check_for_betrayal(node_id), None);
+    }
+
+    // See the comment on `test_policy_enforcer_neutral`: `fuzz_mock`'s flat
+    // trust score doesn't distinguish unknown DIDs, so this only runs
+    // against the real backend.
+    #[test]
+    #[cfg(not(feature = "fuzz_mock"))]
+    fn test_should_accept_packet_with_health_attached_uses_live_client() {
+        let qvl = Arc::new(QvlClient::new().unwrap());
+        let health = crate::supervisor::Supervisor::new(qvl.clone());
+        let enforcer = PolicyEnforcer::new(qvl).with_health(health.clone());
+
+        // A freshly attached supervisor starts Connected, so attaching it
+        // shouldn't change behavior relative to no supervisor at all.
+        assert_eq!(health.qvl_state(), crate::supervisor::HealthState::Connected);
+        let unknown_did = [3u8; 32];
+        assert_eq!(enforcer.should_accept_packet(&unknown_did), PolicyDecision::Neutral);
+    }
 }
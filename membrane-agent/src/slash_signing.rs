@@ -0,0 +1,179 @@
+//! Slash Signing - pluggable authentication for SlashSignals
+//!
+//! `QvlClient::issue_slash_signal` produces an unsigned 82-byte payload.
+//! This module wraps it in a detached Ed25519 signature so a signal can be
+//! broadcast to the network and independently verified, instead of being
+//! trusted on the word of whichever node emitted it.
+
+use thiserror::Error;
+
+#[cfg(all(feature = "rustcrypto", feature = "ring"))]
+compile_error!("features `rustcrypto` and `ring` are mutually exclusive slash-signing backends");
+
+/// Slash signing/verification errors
+#[derive(Error, Debug)]
+pub enum SlashSignError {
+    #[error("signature verification failed")]
+    VerificationFailed,
+
+    #[error("evidence hash does not match the supplied Proof-of-Cycle blob")]
+    EvidenceMismatch,
+
+    #[error("signing backend error: {0}")]
+    Backend(String),
+}
+
+/// An authenticated, network-broadcastable SlashSignal: the raw 82-byte
+/// payload from `QvlClient::issue_slash_signal` plus a detached Ed25519
+/// signature over it, keyed by the issuer's DID.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedSlashSignal {
+    pub signal: [u8; 82],
+    pub signature: [u8; 64],
+    pub issuer: [u8; 32],
+}
+
+impl SignedSlashSignal {
+    /// Evidence hash embedded in the underlying signal (offset 33..65, see
+    /// `QvlClient::issue_slash_signal`).
+    pub fn evidence_hash(&self) -> &[u8] {
+        &self.signal[33..65]
+    }
+}
+
+/// Signs an 82-byte SlashSignal on behalf of an issuer DID.
+pub trait SlashSigner {
+    fn sign(&self, signal: [u8; 82]) -> Result<SignedSlashSignal, SlashSignError>;
+}
+
+/// Verifies a signed SlashSignal: the signature itself, and that the
+/// embedded evidence hash matches an independently supplied Proof-of-Cycle
+/// blob before a receiving node acts on the punishment.
+pub trait SlashVerifier {
+    fn verify(
+        &self,
+        signed: &SignedSlashSignal,
+        expected_evidence_hash: &[u8; 32],
+    ) -> Result<(), SlashSignError>;
+}
+
+#[cfg(feature = "rustcrypto")]
+mod rustcrypto_backend {
+    use super::{SignedSlashSignal, SlashSignError, SlashSigner, SlashVerifier};
+    use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+
+    /// Ed25519 signer backed by the pure-Rust `ed25519-dalek` crate.
+    pub struct RustCryptoSlashSigner {
+        signing_key: SigningKey,
+        issuer: [u8; 32],
+    }
+
+    impl RustCryptoSlashSigner {
+        pub fn new(signing_key: SigningKey, issuer: [u8; 32]) -> Self {
+            Self { signing_key, issuer }
+        }
+    }
+
+    impl SlashSigner for RustCryptoSlashSigner {
+        fn sign(&self, signal: [u8; 82]) -> Result<SignedSlashSignal, SlashSignError> {
+            let signature = self.signing_key.sign(&signal);
+            Ok(SignedSlashSignal {
+                signal,
+                signature: signature.to_bytes(),
+                issuer: self.issuer,
+            })
+        }
+    }
+
+    /// Ed25519 verifier backed by the pure-Rust `ed25519-dalek` crate.
+    pub struct RustCryptoSlashVerifier {
+        verifying_key: VerifyingKey,
+    }
+
+    impl RustCryptoSlashVerifier {
+        pub fn new(verifying_key: VerifyingKey) -> Self {
+            Self { verifying_key }
+        }
+    }
+
+    impl SlashVerifier for RustCryptoSlashVerifier {
+        fn verify(
+            &self,
+            signed: &SignedSlashSignal,
+            expected_evidence_hash: &[u8; 32],
+        ) -> Result<(), SlashSignError> {
+            if signed.evidence_hash() != expected_evidence_hash {
+                return Err(SlashSignError::EvidenceMismatch);
+            }
+
+            let signature = Signature::from_bytes(&signed.signature);
+            self.verifying_key
+                .verify(&signed.signal, &signature)
+                .map_err(|_| SlashSignError::VerificationFailed)
+        }
+    }
+}
+
+#[cfg(feature = "rustcrypto")]
+pub use rustcrypto_backend::{RustCryptoSlashSigner, RustCryptoSlashVerifier};
+
+#[cfg(feature = "ring")]
+mod ring_backend {
+    use super::{SignedSlashSignal, SlashSignError, SlashSigner, SlashVerifier};
+    use ring::signature::{self, Ed25519KeyPair, KeyPair, UnparsedPublicKey};
+
+    /// Ed25519 signer backed by `ring`.
+    pub struct RingSlashSigner {
+        key_pair: Ed25519KeyPair,
+        issuer: [u8; 32],
+    }
+
+    impl RingSlashSigner {
+        pub fn new(key_pair: Ed25519KeyPair, issuer: [u8; 32]) -> Self {
+            Self { key_pair, issuer }
+        }
+    }
+
+    impl SlashSigner for RingSlashSigner {
+        fn sign(&self, signal: [u8; 82]) -> Result<SignedSlashSignal, SlashSignError> {
+            let sig = self.key_pair.sign(&signal);
+            let mut signature = [0u8; 64];
+            signature.copy_from_slice(sig.as_ref());
+            Ok(SignedSlashSignal {
+                signal,
+                signature,
+                issuer: self.issuer,
+            })
+        }
+    }
+
+    /// Ed25519 verifier backed by `ring`.
+    pub struct RingSlashVerifier {
+        public_key: Vec<u8>,
+    }
+
+    impl RingSlashVerifier {
+        pub fn new(public_key: Vec<u8>) -> Self {
+            Self { public_key }
+        }
+    }
+
+    impl SlashVerifier for RingSlashVerifier {
+        fn verify(
+            &self,
+            signed: &SignedSlashSignal,
+            expected_evidence_hash: &[u8; 32],
+        ) -> Result<(), SlashSignError> {
+            if signed.evidence_hash() != expected_evidence_hash {
+                return Err(SlashSignError::EvidenceMismatch);
+            }
+
+            let key = UnparsedPublicKey::new(&signature::ED25519, &self.public_key);
+            key.verify(&signed.signal, &signed.signature)
+                .map_err(|_| SlashSignError::VerificationFailed)
+        }
+    }
+}
+
+#[cfg(feature = "ring")]
+pub use ring_backend::{RingSlashSigner, RingSlashVerifier};
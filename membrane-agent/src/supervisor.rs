@@ -0,0 +1,367 @@
+//! Health supervision for the QVL FFI client and the L0 IPC socket.
+//!
+//! `detect_betrayal`/`get_trust_score` can start failing if the underlying
+//! Zig QVL context degrades, and the L0 peer's socket can vanish without
+//! anything noticing. `Supervisor` periodically probes both and, after
+//! repeated failure, re-initializes the `QvlClient` or re-binds the
+//! `EventListener`, backing off exponentially between attempts so a
+//! persistent outage doesn't spin-loop reconnects.
+
+use crate::did_registry::DidRegistry;
+use crate::event_listener::{EventListener, EventListenerConfig, L0Event};
+use crate::qvl_ffi::QvlClient;
+use std::path::Path;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+/// Current health of one supervised component, as observed by the
+/// supervisor's periodic probes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealthState {
+    /// Last probe succeeded.
+    Connected,
+    /// Probes have started failing, but a reconnect hasn't been triggered
+    /// yet.
+    Degraded,
+    /// Actively re-initializing the QVL client or re-binding the socket
+    /// after repeated failures.
+    Reconnecting,
+}
+
+impl HealthState {
+    fn to_u8(self) -> u8 {
+        match self {
+            HealthState::Connected => 0,
+            HealthState::Degraded => 1,
+            HealthState::Reconnecting => 2,
+        }
+    }
+
+    fn from_u8(v: u8) -> Self {
+        match v {
+            0 => HealthState::Connected,
+            1 => HealthState::Degraded,
+            _ => HealthState::Reconnecting,
+        }
+    }
+}
+
+/// Exponential backoff with a cap and jitter, used between reconnect
+/// attempts for both the QVL client and the L0 socket.
+#[derive(Debug, Clone, Copy)]
+pub struct BackoffConfig {
+    pub initial: Duration,
+    pub max: Duration,
+    pub multiplier: f64,
+    /// Fraction of the computed delay randomized away. `0.0` disables
+    /// jitter.
+    pub jitter_frac: f64,
+}
+
+impl Default for BackoffConfig {
+    fn default() -> Self {
+        Self {
+            initial: Duration::from_millis(500),
+            max: Duration::from_secs(30),
+            multiplier: 2.0,
+            jitter_frac: 0.2,
+        }
+    }
+}
+
+impl BackoffConfig {
+    /// Delay before retry attempt `attempt` (0-based).
+    fn delay_for(&self, attempt: u32) -> Duration {
+        let scaled = self.initial.as_secs_f64() * self.multiplier.powi(attempt as i32);
+        let capped = scaled.min(self.max.as_secs_f64());
+
+        if self.jitter_frac <= 0.0 {
+            return Duration::from_secs_f64(capped);
+        }
+
+        // No `rand` dependency here; the sub-millisecond part of the
+        // current time is unpredictable enough to avoid a thundering herd
+        // of simultaneous reconnects.
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .subsec_nanos();
+        let jitter_unit = (nanos % 1_000_000) as f64 / 1_000_000.0; // [0, 1)
+        let jittered = capped * (1.0 - self.jitter_frac + 2.0 * self.jitter_frac * jitter_unit);
+        Duration::from_secs_f64(jittered.max(0.0))
+    }
+}
+
+/// Consecutive probe failures before declaring `Degraded`.
+const DEGRADE_AFTER: u32 = 2;
+/// Consecutive probe failures before actually reconnecting.
+const RECONNECT_AFTER: u32 = 5;
+
+/// How long the L0 socket must stay `Connected` before a subsequent
+/// failure resets the rebind backoff to attempt 0. Without this, `attempt`
+/// only ever grows, so a handful of old flaps pin every later reconnect at
+/// `BackoffConfig::max` even for the first failure of an otherwise-healthy
+/// socket.
+const SUSTAINED_CONNECTION_SECS: u64 = 60;
+
+/// Whether a socket that was `Connected` for `connected_for` has been up
+/// long enough to treat the next failure as a fresh outage rather than a
+/// continuation of whatever caused earlier rebinds.
+fn should_reset_attempt(connected_for: Duration) -> bool {
+    connected_for >= Duration::from_secs(SUSTAINED_CONNECTION_SECS)
+}
+
+/// Supervises QVL FFI liveness and L0 socket liveness, swapping in a fresh
+/// `QvlClient` or respawning the `EventListener` on repeated failure.
+pub struct Supervisor {
+    qvl: RwLock<Arc<QvlClient>>,
+    qvl_state: AtomicU8,
+    socket_state: AtomicU8,
+    backoff: BackoffConfig,
+}
+
+impl Supervisor {
+    /// Create a supervisor around an already-initialized `QvlClient`, using
+    /// `BackoffConfig::default()`.
+    pub fn new(qvl: Arc<QvlClient>) -> Arc<Self> {
+        Self::with_backoff(qvl, BackoffConfig::default())
+    }
+
+    /// Create a supervisor with custom backoff parameters.
+    pub fn with_backoff(qvl: Arc<QvlClient>, backoff: BackoffConfig) -> Arc<Self> {
+        Arc::new(Self {
+            qvl: RwLock::new(qvl),
+            qvl_state: AtomicU8::new(HealthState::Connected.to_u8()),
+            socket_state: AtomicU8::new(HealthState::Connected.to_u8()),
+            backoff,
+        })
+    }
+
+    /// The QVL client currently in use. Swapped out under the hood after a
+    /// successful reconnect, so callers should re-fetch this rather than
+    /// holding onto it across calls.
+    pub fn qvl(&self) -> Arc<QvlClient> {
+        self.qvl.read().unwrap().clone()
+    }
+
+    /// Current health of the QVL FFI integration.
+    pub fn qvl_state(&self) -> HealthState {
+        HealthState::from_u8(self.qvl_state.load(Ordering::Relaxed))
+    }
+
+    /// Current health of the L0 IPC socket.
+    pub fn socket_state(&self) -> HealthState {
+        HealthState::from_u8(self.socket_state.load(Ordering::Relaxed))
+    }
+
+    fn set_qvl_state(&self, new: HealthState) {
+        let old = self.qvl_state();
+        if old != new {
+            info!("QVL health transition: {:?} -> {:?}", old, new);
+        }
+        self.qvl_state.store(new.to_u8(), Ordering::Relaxed);
+    }
+
+    fn set_socket_state(&self, new: HealthState) {
+        let old = self.socket_state();
+        if old != new {
+            info!("L0 socket health transition: {:?} -> {:?}", old, new);
+        }
+        self.socket_state.store(new.to_u8(), Ordering::Relaxed);
+    }
+
+    /// Cheap liveness probe: a read-only FFI call that should always
+    /// succeed while the QVL context is alive.
+    fn probe_qvl(&self) -> bool {
+        self.qvl().get_reputation(0).is_ok()
+    }
+
+    /// Periodically probe QVL liveness, re-initializing the client with
+    /// exponential backoff after `RECONNECT_AFTER` consecutive failures.
+    /// Never returns; spawn this as a background task.
+    pub async fn run_qvl_health_check(self: Arc<Self>, check_interval: Duration) {
+        let mut consecutive_failures = 0u32;
+
+        loop {
+            tokio::time::sleep(check_interval).await;
+
+            if self.probe_qvl() {
+                consecutive_failures = 0;
+                self.set_qvl_state(HealthState::Connected);
+                continue;
+            }
+
+            consecutive_failures += 1;
+
+            if consecutive_failures >= RECONNECT_AFTER {
+                self.set_qvl_state(HealthState::Reconnecting);
+                self.reconnect_qvl(&mut consecutive_failures).await;
+            } else if consecutive_failures >= DEGRADE_AFTER {
+                self.set_qvl_state(HealthState::Degraded);
+            }
+        }
+    }
+
+    /// Re-initialize the QVL client with exponential backoff until a probe
+    /// against the fresh client succeeds.
+    async fn reconnect_qvl(&self, consecutive_failures: &mut u32) {
+        let mut attempt = 0u32;
+        loop {
+            let delay = self.backoff.delay_for(attempt);
+            warn!("QVL reconnect attempt {} in {:?}", attempt + 1, delay);
+            tokio::time::sleep(delay).await;
+
+            match QvlClient::new() {
+                Ok(fresh) => {
+                    *self.qvl.write().unwrap() = Arc::new(fresh);
+                    if self.probe_qvl() {
+                        info!("QVL client re-initialized successfully");
+                        *consecutive_failures = 0;
+                        self.set_qvl_state(HealthState::Connected);
+                        return;
+                    }
+                }
+                Err(e) => warn!("QVL re-init failed: {}", e),
+            }
+
+            attempt += 1;
+        }
+    }
+
+    /// Spawn a supervised `EventListener`: (re)binds the L0 socket,
+    /// forwards every `L0Event` to the returned receiver, and respawns
+    /// with exponential backoff whenever the listener exits (bind failure)
+    /// or its socket file vanishes out from under it.
+    pub fn spawn_event_listener(
+        self: Arc<Self>,
+        config: EventListenerConfig,
+        did_registry: Option<Arc<DidRegistry>>,
+        check_interval: Duration,
+    ) -> (mpsc::Receiver<L0Event>, JoinHandle<()>) {
+        let (out_tx, out_rx) = mpsc::channel(config.buffer_size);
+        let handle = tokio::spawn(self.run_event_listener(config, did_registry, out_tx, check_interval));
+        (out_rx, handle)
+    }
+
+    async fn run_event_listener(
+        self: Arc<Self>,
+        config: EventListenerConfig,
+        did_registry: Option<Arc<DidRegistry>>,
+        out_tx: mpsc::Sender<L0Event>,
+        check_interval: Duration,
+    ) {
+        let mut attempt = 0u32;
+
+        loop {
+            let (listener, mut inner_rx) = EventListener::new(config.clone());
+            let listener = match &did_registry {
+                Some(registry) => listener.with_did_registry(registry.clone()),
+                None => listener,
+            };
+            let socket_path = listener.socket_path().to_string();
+
+            let forward = {
+                let out_tx = out_tx.clone();
+                tokio::spawn(async move {
+                    while let Some(event) = inner_rx.recv().await {
+                        if out_tx.send(event).await.is_err() {
+                            break;
+                        }
+                    }
+                })
+            };
+
+            let mut serve = tokio::spawn(async move { listener.start().await });
+
+            // Watch the socket file while serving; if it vanishes out from
+            // under us (e.g. its directory got cleaned up), force a
+            // restart the same way a bind failure would.
+            let watchdog_path = socket_path.clone();
+            let mut watchdog = tokio::spawn(async move {
+                loop {
+                    tokio::time::sleep(check_interval).await;
+                    if !Path::new(&watchdog_path).exists() {
+                        return;
+                    }
+                }
+            });
+
+            self.set_socket_state(HealthState::Connected);
+            let connected_at = Instant::now();
+
+            tokio::select! {
+                result = &mut serve => {
+                    watchdog.abort();
+                    match result {
+                        Ok(Ok(())) => {}
+                        Ok(Err(e)) => warn!("L0 socket '{}' failed: {}", socket_path, e),
+                        Err(e) => warn!("L0 listener task panicked: {}", e),
+                    }
+                }
+                _ = &mut watchdog => {
+                    warn!("L0 socket '{}' vanished; forcing reconnect", socket_path);
+                    serve.abort();
+                }
+            }
+            forward.abort();
+
+            if should_reset_attempt(connected_at.elapsed()) {
+                attempt = 0;
+            }
+
+            self.set_socket_state(HealthState::Reconnecting);
+            let delay = self.backoff.delay_for(attempt);
+            warn!("Rebinding L0 socket '{}' in {:?}", socket_path, delay);
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_caps_at_max() {
+        let backoff = BackoffConfig {
+            initial: Duration::from_millis(100),
+            max: Duration::from_secs(1),
+            multiplier: 10.0,
+            jitter_frac: 0.0,
+        };
+
+        // With no jitter, a large attempt count must still clamp to `max`.
+        assert_eq!(backoff.delay_for(10), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_backoff_grows_with_attempt() {
+        let backoff = BackoffConfig {
+            initial: Duration::from_millis(100),
+            max: Duration::from_secs(30),
+            multiplier: 2.0,
+            jitter_frac: 0.0,
+        };
+
+        assert!(backoff.delay_for(2) > backoff.delay_for(0));
+    }
+
+    #[test]
+    fn test_should_reset_attempt_requires_sustained_connection() {
+        assert!(!should_reset_attempt(Duration::from_secs(1)));
+        assert!(should_reset_attempt(Duration::from_secs(SUSTAINED_CONNECTION_SECS)));
+    }
+
+    #[test]
+    fn test_supervisor_qvl_state_starts_connected() {
+        let qvl = Arc::new(QvlClient::new().unwrap());
+        let supervisor = Supervisor::new(qvl);
+        assert_eq!(supervisor.qvl_state(), HealthState::Connected);
+        assert_eq!(supervisor.socket_state(), HealthState::Connected);
+    }
+}
@@ -0,0 +1,396 @@
+//! Versioned TLV wire codec for Proof-of-Cycle evidence and SlashSignals
+//!
+//! `QvlClient::get_betrayal_evidence` returns an opaque `Vec<u8>` and
+//! `QvlClient::issue_slash_signal` returns a fixed 82-byte blob parsed by
+//! hardcoded offsets, both of which are fragile across versions and nodes.
+//! This module adds a self-describing tag-length-value format — an
+//! explicit version byte followed by `(tag, u32 length, value)` fields —
+//! for both payloads, so a verifier on a different node/version can parse
+//! them even as the underlying Zig struct layout evolves.
+
+use crate::qvl_ffi::QvlRiskEdge;
+use alloc::vec;
+use alloc::vec::Vec;
+use thiserror::Error;
+
+/// Current wire format version for both encodings in this module.
+pub const TLV_VERSION: u8 = 1;
+
+/// Top-level tags for the betrayal-evidence (negative-cycle edge list)
+/// encoding.
+mod evidence_tag {
+    pub const EDGE: u8 = 0x01;
+}
+
+/// Field tags within a single encoded `EDGE` entry.
+mod edge_tag {
+    pub const FROM: u8 = 0x01;
+    pub const TO: u8 = 0x02;
+    pub const RISK: u8 = 0x03;
+    pub const TIMESTAMP_NS: u8 = 0x04;
+    pub const NONCE: u8 = 0x05;
+    pub const EXPIRES_AT_NS: u8 = 0x06;
+}
+
+/// Top-level tags for the SlashSignal encoding.
+mod slash_tag {
+    pub const TARGET_DID: u8 = 0x01;
+    pub const REASON: u8 = 0x02;
+    pub const EVIDENCE_HASH: u8 = 0x03;
+    pub const SEVERITY: u8 = 0x04;
+}
+
+/// TLV codec errors
+#[derive(Error, Debug)]
+pub enum TlvError {
+    #[error("buffer too short to contain a TLV field")]
+    Truncated,
+
+    #[error("unsupported TLV version: {0}")]
+    UnsupportedVersion(u8),
+
+    #[error("unexpected length for field {tag:#x}: expected {expected}, got {got}")]
+    BadFieldLength { tag: u8, expected: usize, got: usize },
+
+    #[error("missing required field {0:#x}")]
+    MissingField(u8),
+
+    #[error("edges do not sum to a negative cycle (total risk {0})")]
+    NotNegativeCycle(f64),
+
+    #[error("edge {from}->{to} is expired relative to now_ns={now_ns}")]
+    ExpiredEdge { from: u32, to: u32, now_ns: u64 },
+}
+
+/// A decoded, independently-verifiable Proof-of-Cycle: the edges making up
+/// a negative-risk trust cycle, plus their combined risk.
+#[derive(Debug, Clone)]
+pub struct ProofOfCycle {
+    pub edges: Vec<QvlRiskEdge>,
+    pub total_risk: f64,
+}
+
+/// TLV-decoded SlashSignal fields (see `QvlClient::issue_slash_signal` for
+/// the fixed-offset 82-byte predecessor this replaces on the wire).
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlashSignalFields {
+    pub target_did: [u8; 32],
+    pub reason: u8,
+    pub evidence_hash: [u8; 32],
+    pub severity: u8,
+}
+
+// ============================================================================
+// Low-level TLV primitives
+// ============================================================================
+
+fn write_field(out: &mut Vec<u8>, tag: u8, value: &[u8]) {
+    out.push(tag);
+    out.extend_from_slice(&(value.len() as u32).to_le_bytes());
+    out.extend_from_slice(value);
+}
+
+/// Iterates `(tag, value)` pairs out of a TLV-encoded buffer (version byte
+/// already stripped by the caller). Unknown tags are left for the caller to
+/// skip, which is what keeps the format forward-compatible.
+struct FieldReader<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> FieldReader<'a> {
+    fn new(buf: &'a [u8]) -> Self {
+        Self { buf }
+    }
+
+    fn next_field(&mut self) -> Result<Option<(u8, &'a [u8])>, TlvError> {
+        if self.buf.is_empty() {
+            return Ok(None);
+        }
+        if self.buf.len() < 5 {
+            return Err(TlvError::Truncated);
+        }
+        let tag = self.buf[0];
+        let len =
+            u32::from_le_bytes([self.buf[1], self.buf[2], self.buf[3], self.buf[4]]) as usize;
+        let rest = &self.buf[5..];
+        if rest.len() < len {
+            return Err(TlvError::Truncated);
+        }
+        let value = &rest[..len];
+        self.buf = &rest[len..];
+        Ok(Some((tag, value)))
+    }
+}
+
+fn read_u32(tag: u8, value: &[u8]) -> Result<u32, TlvError> {
+    value
+        .try_into()
+        .map(u32::from_le_bytes)
+        .map_err(|_| TlvError::BadFieldLength { tag, expected: 4, got: value.len() })
+}
+
+fn read_u64(tag: u8, value: &[u8]) -> Result<u64, TlvError> {
+    value
+        .try_into()
+        .map(u64::from_le_bytes)
+        .map_err(|_| TlvError::BadFieldLength { tag, expected: 8, got: value.len() })
+}
+
+fn read_f64(tag: u8, value: &[u8]) -> Result<f64, TlvError> {
+    value
+        .try_into()
+        .map(f64::from_le_bytes)
+        .map_err(|_| TlvError::BadFieldLength { tag, expected: 8, got: value.len() })
+}
+
+fn read_array32(tag: u8, value: &[u8]) -> Result<[u8; 32], TlvError> {
+    value
+        .try_into()
+        .map_err(|_| TlvError::BadFieldLength { tag, expected: 32, got: value.len() })
+}
+
+fn read_u8(tag: u8, value: &[u8]) -> Result<u8, TlvError> {
+    match value {
+        [byte] => Ok(*byte),
+        _ => Err(TlvError::BadFieldLength { tag, expected: 1, got: value.len() }),
+    }
+}
+
+// ============================================================================
+// Betrayal evidence (negative-cycle edge list)
+// ============================================================================
+
+/// Encode a negative-cycle edge list (as returned by the Zig betrayal
+/// detector) into the versioned TLV wire format.
+pub fn encode_evidence(edges: &[QvlRiskEdge]) -> Vec<u8> {
+    let mut out = vec![TLV_VERSION];
+    for edge in edges {
+        let mut edge_buf = Vec::new();
+        write_field(&mut edge_buf, edge_tag::FROM, &edge.from.to_le_bytes());
+        write_field(&mut edge_buf, edge_tag::TO, &edge.to.to_le_bytes());
+        write_field(&mut edge_buf, edge_tag::RISK, &edge.risk.to_le_bytes());
+        write_field(&mut edge_buf, edge_tag::TIMESTAMP_NS, &edge.timestamp_ns.to_le_bytes());
+        write_field(&mut edge_buf, edge_tag::NONCE, &edge.nonce.to_le_bytes());
+        write_field(&mut edge_buf, edge_tag::EXPIRES_AT_NS, &edge.expires_at_ns.to_le_bytes());
+        write_field(&mut out, evidence_tag::EDGE, &edge_buf);
+    }
+    out
+}
+
+/// Decode and validate a TLV-encoded betrayal evidence blob into a
+/// `ProofOfCycle`. Validates that the reconstructed cycle actually sums to
+/// negative risk and that every edge is unexpired relative to `now_ns`, so
+/// a verifier can independently confirm a `BetrayalNegativeCycle` reason
+/// before acting on a slash.
+pub fn decode_evidence(buf: &[u8], now_ns: u64) -> Result<ProofOfCycle, TlvError> {
+    let (&version, rest) = buf.split_first().ok_or(TlvError::Truncated)?;
+    if version != TLV_VERSION {
+        return Err(TlvError::UnsupportedVersion(version));
+    }
+
+    let mut reader = FieldReader::new(rest);
+    let mut edges = Vec::new();
+    while let Some((tag, value)) = reader.next_field()? {
+        if tag == evidence_tag::EDGE {
+            edges.push(decode_edge(value, now_ns)?);
+        }
+        // Unknown top-level tags are skipped: that's what keeps this
+        // format forward-compatible with a future encoder.
+    }
+
+    let total_risk: f64 = edges.iter().map(|e| e.risk).sum();
+    if total_risk >= 0.0 {
+        return Err(TlvError::NotNegativeCycle(total_risk));
+    }
+
+    Ok(ProofOfCycle { edges, total_risk })
+}
+
+fn decode_edge(buf: &[u8], now_ns: u64) -> Result<QvlRiskEdge, TlvError> {
+    let mut reader = FieldReader::new(buf);
+    let mut from = None;
+    let mut to = None;
+    let mut risk = None;
+    let mut timestamp_ns = None;
+    let mut nonce = None;
+    let mut expires_at_ns = None;
+
+    while let Some((tag, value)) = reader.next_field()? {
+        match tag {
+            edge_tag::FROM => from = Some(read_u32(tag, value)?),
+            edge_tag::TO => to = Some(read_u32(tag, value)?),
+            edge_tag::RISK => risk = Some(read_f64(tag, value)?),
+            edge_tag::TIMESTAMP_NS => timestamp_ns = Some(read_u64(tag, value)?),
+            edge_tag::NONCE => nonce = Some(read_u64(tag, value)?),
+            edge_tag::EXPIRES_AT_NS => expires_at_ns = Some(read_u64(tag, value)?),
+            _ => {} // forward-compatible: ignore unknown edge fields
+        }
+    }
+
+    let from = from.ok_or(TlvError::MissingField(edge_tag::FROM))?;
+    let to = to.ok_or(TlvError::MissingField(edge_tag::TO))?;
+    let risk = risk.ok_or(TlvError::MissingField(edge_tag::RISK))?;
+    let timestamp_ns = timestamp_ns.ok_or(TlvError::MissingField(edge_tag::TIMESTAMP_NS))?;
+    let nonce = nonce.ok_or(TlvError::MissingField(edge_tag::NONCE))?;
+    let expires_at_ns =
+        expires_at_ns.ok_or(TlvError::MissingField(edge_tag::EXPIRES_AT_NS))?;
+
+    if expires_at_ns <= now_ns {
+        return Err(TlvError::ExpiredEdge { from, to, now_ns });
+    }
+
+    Ok(QvlRiskEdge {
+        from,
+        to,
+        risk,
+        timestamp_ns,
+        nonce,
+        // `level` isn't part of the Proof-of-Cycle wire format (it's not
+        // needed to independently verify a negative cycle); callers that
+        // need it should read it from the original FFI struct.
+        level: 0,
+        expires_at_ns,
+    })
+}
+
+// ============================================================================
+// SlashSignal fields
+// ============================================================================
+
+/// Encode SlashSignal fields into the versioned TLV wire format.
+pub fn encode_slash_fields(fields: &SlashSignalFields) -> Vec<u8> {
+    let mut out = vec![TLV_VERSION];
+    write_field(&mut out, slash_tag::TARGET_DID, &fields.target_did);
+    write_field(&mut out, slash_tag::REASON, &[fields.reason]);
+    write_field(&mut out, slash_tag::EVIDENCE_HASH, &fields.evidence_hash);
+    write_field(&mut out, slash_tag::SEVERITY, &[fields.severity]);
+    out
+}
+
+/// Decode TLV-encoded SlashSignal fields.
+pub fn decode_slash_fields(buf: &[u8]) -> Result<SlashSignalFields, TlvError> {
+    let (&version, rest) = buf.split_first().ok_or(TlvError::Truncated)?;
+    if version != TLV_VERSION {
+        return Err(TlvError::UnsupportedVersion(version));
+    }
+
+    let mut reader = FieldReader::new(rest);
+    let mut target_did = None;
+    let mut reason = None;
+    let mut evidence_hash = None;
+    let mut severity = None;
+
+    while let Some((tag, value)) = reader.next_field()? {
+        match tag {
+            slash_tag::TARGET_DID => target_did = Some(read_array32(tag, value)?),
+            slash_tag::REASON => reason = Some(read_u8(tag, value)?),
+            slash_tag::EVIDENCE_HASH => evidence_hash = Some(read_array32(tag, value)?),
+            slash_tag::SEVERITY => severity = Some(read_u8(tag, value)?),
+            _ => {} // forward-compatible: ignore unknown fields
+        }
+    }
+
+    Ok(SlashSignalFields {
+        target_did: target_did.ok_or(TlvError::MissingField(slash_tag::TARGET_DID))?,
+        reason: reason.ok_or(TlvError::MissingField(slash_tag::REASON))?,
+        evidence_hash: evidence_hash.ok_or(TlvError::MissingField(slash_tag::EVIDENCE_HASH))?,
+        severity: severity.ok_or(TlvError::MissingField(slash_tag::SEVERITY))?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_cycle() -> Vec<QvlRiskEdge> {
+        vec![
+            QvlRiskEdge {
+                from: 1,
+                to: 2,
+                risk: -0.5,
+                timestamp_ns: 1_000,
+                nonce: 1,
+                level: 3,
+                expires_at_ns: 10_000,
+            },
+            QvlRiskEdge {
+                from: 2,
+                to: 1,
+                risk: -0.5,
+                timestamp_ns: 1_000,
+                nonce: 2,
+                level: 3,
+                expires_at_ns: 10_000,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_evidence_round_trip() {
+        let edges = sample_cycle();
+        let encoded = encode_evidence(&edges);
+        let proof = decode_evidence(&encoded, 5_000).expect("valid negative cycle");
+
+        assert_eq!(proof.edges.len(), 2);
+        assert_eq!(proof.total_risk, -1.0);
+        assert_eq!(proof.edges[0].from, 1);
+        assert_eq!(proof.edges[0].to, 2);
+    }
+
+    #[test]
+    fn test_evidence_rejects_non_negative_cycle() {
+        let mut edges = sample_cycle();
+        edges[0].risk = 0.5; // now sums to 0.0, not negative
+
+        let encoded = encode_evidence(&edges);
+        let err = decode_evidence(&encoded, 5_000).unwrap_err();
+        assert!(matches!(err, TlvError::NotNegativeCycle(_)));
+    }
+
+    #[test]
+    fn test_evidence_rejects_expired_edge() {
+        let edges = sample_cycle();
+        let encoded = encode_evidence(&edges);
+
+        // now_ns past every edge's expires_at_ns (10_000)
+        let err = decode_evidence(&encoded, 20_000).unwrap_err();
+        assert!(matches!(err, TlvError::ExpiredEdge { .. }));
+    }
+
+    #[test]
+    fn test_evidence_rejects_unsupported_version() {
+        let mut encoded = encode_evidence(&sample_cycle());
+        encoded[0] = 0xFF;
+        let err = decode_evidence(&encoded, 5_000).unwrap_err();
+        assert!(matches!(err, TlvError::UnsupportedVersion(0xFF)));
+    }
+
+    #[test]
+    fn test_slash_fields_round_trip() {
+        let fields = SlashSignalFields {
+            target_did: [0xAAu8; 32],
+            reason: 1,
+            evidence_hash: [0xEEu8; 32],
+            severity: 9,
+        };
+
+        let encoded = encode_slash_fields(&fields);
+        let decoded = decode_slash_fields(&encoded).expect("valid fields");
+        assert_eq!(decoded, fields);
+    }
+
+    #[test]
+    fn test_slash_fields_missing_field() {
+        // Truncate after the version byte + first field, dropping the rest.
+        let fields = SlashSignalFields {
+            target_did: [0xAAu8; 32],
+            reason: 1,
+            evidence_hash: [0xEEu8; 32],
+            severity: 9,
+        };
+        let full = encode_slash_fields(&fields);
+        let truncated = &full[..6]; // version + partial TARGET_DID field
+        let err = decode_slash_fields(truncated).unwrap_err();
+        assert!(matches!(err, TlvError::Truncated));
+    }
+}
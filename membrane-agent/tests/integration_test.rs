@@ -1,10 +1,8 @@
 use membrane_agent::{
-    QvlClient, PolicyEnforcer, AnomalyAlertSystem, 
-    L0Event, PolicyDecision, QvlRiskEdge,
-    AnomalyReason
+    QvlClient, PolicyEnforcer, AnomalyAlertSystem,
+    QvlRiskEdge, AnomalyReason
 };
 use std::sync::Arc;
-use tokio::time::Duration;
 
 #[tokio::test]
 async fn test_full_pipeline_integration() {
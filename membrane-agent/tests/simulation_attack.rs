@@ -1,16 +1,30 @@
 //! Simulation Attack - Red Team Live Fire Exercise
+//!
+//! `fuzz_mock` swaps in a stateless stand-in for the QVL FFI (flat trust
+//! scores, no real negative-cycle detection — see `qvl_ffi::fuzz_mock`), so
+//! this live-fire scenario can only actually trigger detection against the
+//! real backend; the whole file is gated off under `fuzz_mock`.
+#![cfg(not(feature = "fuzz_mock"))]
 
+use ed25519_dalek::SigningKey;
 use membrane_agent::qvl_ffi::{QvlClient, QvlRiskEdge};
 use membrane_agent::policy_enforcer::PolicyEnforcer;
+use membrane_agent::slash_signing::RustCryptoSlashSigner;
+use rand_core::OsRng;
 use std::sync::Arc;
 
 #[test]
 fn test_live_fire_betrayal_simulation() {
     println!(">>> INITIATING BETRAYAL SIMULATION <<<");
-    
+
     // 1. Init
     let qvl = Arc::new(QvlClient::new().unwrap());
-    let enforcer = PolicyEnforcer::new(qvl.clone());
+    let local_did = [0x01u8; 32];
+    let signer = Arc::new(RustCryptoSlashSigner::new(
+        SigningKey::generate(&mut OsRng),
+        local_did,
+    ));
+    let enforcer = PolicyEnforcer::new(qvl.clone()).with_signer(signer);
     
     // 2. Register Actors
     let traitor_did = [0xAAu8; 32];
@@ -58,25 +72,28 @@ fn test_live_fire_betrayal_simulation() {
     let punishment = enforcer.punish_if_guilty(traitor_id);
     
     match punishment {
-        Some(signal) => {
-            println!("[!] BETRAYAL DETECTED! Slash Signal Generated.");
-            println!("[!] Payload Size: {} bytes", signal.len());
-            
+        Some(signed) => {
+            println!("[!] BETRAYAL DETECTED! Signed Slash Signal Generated.");
+            println!("[!] Payload Size: {} bytes", signed.signal.len());
+
             // Verify content
             // Target DID should be Traitor DID (first 32 bytes)
-            assert_eq!(&signal[0..32], &traitor_did);
-            
+            assert_eq!(&signed.signal[0..32], &traitor_did);
+
             // Reason should be BetrayalCycle (1)
-            assert_eq!(signal[32], 1);
-            
+            assert_eq!(signed.signal[32], 1);
+
             // Evidence Payload should be present (offset 33..65)
             let evidence_start = 33;
             // First byte of evidence hash should match mock (0xEE) or real
             // Since we implemented Mock Hash 0xEE in PolicyEnforcer for now:
-            assert_eq!(signal[evidence_start], 0xEE);
-            
+            assert_eq!(signed.signal[evidence_start], 0xEE);
+
+            // Issuer is the local node's signing DID, not the traitor's.
+            assert_eq!(signed.issuer, local_did);
+
             println!("[+] SUCCESS: Traitor identified and sentenced.");
-            println!("[+] Target DID matches expectation: {:X?}", &signal[0..4]);
+            println!("[+] Target DID matches expectation: {:X?}", &signed.signal[0..4]);
         },
         None => {
             // Debugging